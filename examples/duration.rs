@@ -0,0 +1,38 @@
+use bevy::prelude::*;
+use bevy_asky::prelude::*;
+use std::time::Duration;
+
+#[path = "common/lib.rs"]
+mod common;
+use common::View;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, AskyPlugin))
+        .add_plugins(common::views)
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    // UI camera
+    commands.spawn(Camera2d);
+    commands
+        .spawn(Node {
+            flex_direction: FlexDirection::Column,
+            ..default()
+        })
+        .with_children(|parent| {
+            parent
+                .construct::<View>(())
+                .construct::<DurationPrompt>("Cooldown? ")
+                .insert(
+                    DurationPrompt::default()
+                        .min(Duration::from_millis(100))
+                        .max(Duration::from_secs(24 * 60 * 60)),
+                )
+                .observe(move |trigger: Trigger<Submit<Duration>>| {
+                    eprintln!("trigger {:?}", trigger.event());
+                });
+        });
+}
@@ -25,7 +25,7 @@ fn setup(mut commands: Commands) {
             parent
                 .construct::<View>(())
                 .construct::<Number<i8>>("Age? ")
-                .construct::<Placeholder>("333")
+                .insert(Number::<i8>::new().default(33).min(0).max(120).clamp())
                 .observe(move |trigger: Trigger<Submit<i8>>| {
                     eprintln!("trigger {:?}", trigger.event());
                 });
@@ -22,6 +22,20 @@ fn setup(mut commands: Commands) {
             .construct::<View>(())
             .construct::<TextField>("Name? ")
             .construct::<Placeholder>("John Smith")
+            .construct::<InputFilter>(
+                InputFilter::default()
+                    .max_len(16)
+                    .allow(CharClass::Alphanumeric)
+                    .allow(CharClass::Whitespace)
+                    .warn(),
+            )
+            .construct::<Validator<String>>(Validator::new(|name: &String| {
+                if name.trim().is_empty() {
+                    Err("name required".into())
+                } else {
+                    Ok(())
+                }
+            }))
             .observe(move |trigger: Trigger<Submit<String>>| {
                 eprintln!("trigger {:?}", trigger.event());
             });
@@ -0,0 +1,292 @@
+//! Arithmetic expressions for [`Number`] prompts
+//!
+//! [`Number`]: crate::Number
+use crate::num_like::{NumLike, NumberFormat};
+use std::{iter::Peekable, str::CharIndices};
+use thiserror::Error;
+
+/// Expression errors
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ExprError {
+    /// Malformed expression
+    #[error("invalid expression")]
+    Syntax,
+    /// Result or intermediate value out of range
+    #[error("overflow")]
+    Overflow,
+    /// Division by zero
+    #[error("division by zero")]
+    DivisionByZero,
+    /// Fractional result for an integer
+    #[error("not an integer")]
+    NotInteger,
+}
+
+/// May `ch` appear in an expression?
+pub(crate) fn is_valid(format: &NumberFormat, ch: char) -> bool {
+    matches!(ch, '+' | '-' | '*' | '/' | '%' | '(' | ')' | ' ')
+        || ch.is_ascii_alphanumeric()
+        || ch == format.decimal
+        || Some(ch) == format.grouping
+}
+
+/// Evaluate an expression of `+`, `-`, `*`, `/`, `%`, and parentheses.
+///
+/// Integer types are evaluated exactly and floats in `f64`.
+pub(crate) fn eval<T: NumLike>(s: &str, format: &NumberFormat) -> Result<T, ExprError> {
+    if T::is_float() {
+        let x = Parser::<f64>::new(s, format).parse()?;
+        let result: T = x.to_string().parse().map_err(|_| ExprError::Overflow)?;
+        // Floats parse out-of-range values as infinite, and `inf - inf` is NaN.
+        if !result.is_finite() {
+            return Err(ExprError::Overflow);
+        }
+        Ok(result)
+    } else {
+        let x = Parser::<i128>::new(s, format).parse()?;
+        x.to_string().parse().map_err(|_| ExprError::Overflow)
+    }
+}
+
+/// Arithmetic for the parser
+trait Arith: Sized + Copy {
+    fn literal(token: &str, format: &NumberFormat) -> Result<Self, ExprError>;
+    fn apply(self, op: char, rhs: Self) -> Result<Self, ExprError>;
+    fn neg(self) -> Result<Self, ExprError>;
+}
+
+impl Arith for i128 {
+    fn literal(token: &str, format: &NumberFormat) -> Result<Self, ExprError> {
+        match format.parse::<i128>(token) {
+            Some(x) => Ok(x),
+            None if format.parse::<f64>(token).is_some() => Err(ExprError::NotInteger),
+            None => Err(ExprError::Syntax),
+        }
+    }
+
+    fn apply(self, op: char, rhs: Self) -> Result<Self, ExprError> {
+        if matches!(op, '/' | '%') && rhs == 0 {
+            return Err(ExprError::DivisionByZero);
+        }
+        if op == '/' && self.checked_rem(rhs).ok_or(ExprError::Overflow)? != 0 {
+            return Err(ExprError::NotInteger);
+        }
+        match op {
+            '+' => self.checked_add(rhs),
+            '-' => self.checked_sub(rhs),
+            '*' => self.checked_mul(rhs),
+            '/' => self.checked_div(rhs),
+            '%' => self.checked_rem(rhs),
+            _ => return Err(ExprError::Syntax),
+        }
+        .ok_or(ExprError::Overflow)
+    }
+
+    fn neg(self) -> Result<Self, ExprError> {
+        self.checked_neg().ok_or(ExprError::Overflow)
+    }
+}
+
+impl Arith for f64 {
+    fn literal(token: &str, format: &NumberFormat) -> Result<Self, ExprError> {
+        format.parse::<f64>(token).ok_or(ExprError::Syntax)
+    }
+
+    fn apply(self, op: char, rhs: Self) -> Result<Self, ExprError> {
+        if matches!(op, '/' | '%') && rhs == 0.0 {
+            return Err(ExprError::DivisionByZero);
+        }
+        Ok(match op {
+            '+' => self + rhs,
+            '-' => self - rhs,
+            '*' => self * rhs,
+            '/' => self / rhs,
+            '%' => self % rhs,
+            _ => return Err(ExprError::Syntax),
+        })
+    }
+
+    fn neg(self) -> Result<Self, ExprError> {
+        Ok(-self)
+    }
+}
+
+/// Recursive descent parser that evaluates as it goes
+///
+/// ```text
+/// expr  := term (('+' | '-') term)*
+/// term  := unary (('*' | '/' | '%') unary)*
+/// unary := ('+' | '-') unary | '(' expr ')' | number
+/// ```
+struct Parser<'a, T> {
+    s: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+    format: &'a NumberFormat,
+    depth: usize,
+    _marker: std::marker::PhantomData<T>,
+}
+
+/// Limit nesting so a long input cannot overflow the stack.
+const MAX_DEPTH: usize = 64;
+
+impl<'a, T: Arith> Parser<'a, T> {
+    fn new(s: &'a str, format: &'a NumberFormat) -> Self {
+        Parser {
+            s,
+            chars: s.char_indices().peekable(),
+            format,
+            depth: 0,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    fn parse(mut self) -> Result<T, ExprError> {
+        let x = self.expr()?;
+        match self.peek() {
+            None => Ok(x),
+            Some(_) => Err(ExprError::Syntax),
+        }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        while self.chars.next_if(|(_, ch)| ch.is_whitespace()).is_some() {}
+        self.chars.peek().map(|(_, ch)| *ch)
+    }
+
+    fn expr(&mut self) -> Result<T, ExprError> {
+        let mut x = self.term()?;
+        while let Some(op @ ('+' | '-')) = self.peek() {
+            self.chars.next();
+            x = x.apply(op, self.term()?)?;
+        }
+        Ok(x)
+    }
+
+    fn term(&mut self) -> Result<T, ExprError> {
+        let mut x = self.unary()?;
+        while let Some(op @ ('*' | '/' | '%')) = self.peek() {
+            self.chars.next();
+            x = x.apply(op, self.unary()?)?;
+        }
+        Ok(x)
+    }
+
+    fn unary(&mut self) -> Result<T, ExprError> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(ExprError::Syntax);
+        }
+        let x = match self.peek() {
+            Some('+') => {
+                self.chars.next();
+                self.unary()
+            }
+            Some('-') => {
+                self.chars.next();
+                self.unary()?.neg()
+            }
+            Some('(') => {
+                self.chars.next();
+                let x = self.expr()?;
+                match self.peek() {
+                    Some(')') => {
+                        self.chars.next();
+                        Ok(x)
+                    }
+                    _ => Err(ExprError::Syntax),
+                }
+            }
+            Some(_) => self.number(),
+            None => Err(ExprError::Syntax),
+        };
+        self.depth -= 1;
+        x
+    }
+
+    fn number(&mut self) -> Result<T, ExprError> {
+        let start = self
+            .chars
+            .peek()
+            .map(|(i, _)| *i)
+            .ok_or(ExprError::Syntax)?;
+        let mut end = start;
+        let mut prev = None;
+        while let Some((i, ch)) = self.chars.next_if(|(_, ch)| {
+            ch.is_ascii_alphanumeric()
+                || *ch == self.format.decimal
+                || Some(*ch) == self.format.grouping
+                // Exponent sign as in `1e-3`
+                || (self.format.scientific
+                    && matches!(ch, '+' | '-')
+                    && matches!(prev, Some('e' | 'E')))
+        }) {
+            prev = Some(ch);
+            end = i + ch.len_utf8();
+        }
+        if start == end {
+            return Err(ExprError::Syntax);
+        }
+        T::literal(&self.s[start..end], self.format)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn integers() {
+        let format = NumberFormat::default();
+        assert_eq!(eval::<u32>("64*3+8", &format), Ok(200));
+        assert_eq!(eval::<i32>("-(2 + 3) * 4", &format), Ok(-20));
+        assert_eq!(eval::<i32>("7 % 4 - 10 / 5", &format), Ok(1));
+        assert_eq!(eval::<i32>("1/3", &format), Err(ExprError::NotInteger));
+        assert_eq!(eval::<i32>("1.5", &format), Err(ExprError::NotInteger));
+        assert_eq!(eval::<i32>("1/0", &format), Err(ExprError::DivisionByZero));
+        assert_eq!(eval::<u8>("200+100", &format), Err(ExprError::Overflow));
+        assert_eq!(eval::<u8>("1-2", &format), Err(ExprError::Overflow));
+        assert_eq!(eval::<i32>("(1+2", &format), Err(ExprError::Syntax));
+        assert_eq!(eval::<i32>("1 2", &format), Err(ExprError::Syntax));
+        assert_eq!(eval::<i32>("", &format), Err(ExprError::Syntax));
+        assert_eq!(
+            eval::<i128>("(-170141183460469231731687303715884105727-1)/-1", &format),
+            Err(ExprError::Overflow)
+        );
+    }
+
+    #[test]
+    fn floats() {
+        let format = NumberFormat::default().scientific();
+        assert_eq!(eval::<f64>("1/4", &format), Ok(0.25));
+        assert_eq!(eval::<f64>("2 * 1e-3", &format), Ok(0.002));
+        assert_eq!(eval::<f64>("1/0", &format), Err(ExprError::DivisionByZero));
+        assert_eq!(
+            eval::<f64>("1e300*1e300", &format),
+            Err(ExprError::Overflow)
+        );
+        assert_eq!(eval::<f32>("1e300", &format), Err(ExprError::Overflow));
+        assert_eq!(
+            eval::<f64>("1e308*10-1e308*10", &format),
+            Err(ExprError::Overflow)
+        );
+    }
+
+    #[test]
+    fn formats() {
+        let format = NumberFormat::default()
+            .decimal(',')
+            .grouping('.')
+            .radix_prefixes();
+        assert_eq!(eval::<f64>("1.000,5 * 2", &format), Ok(2001.0));
+        assert_eq!(eval::<u32>("0xFF + 1", &format), Ok(256));
+    }
+
+    #[test]
+    fn deep_nesting() {
+        let s = "(".repeat(1000);
+        assert_eq!(
+            eval::<i32>(&s, &NumberFormat::default()),
+            Err(ExprError::Syntax)
+        );
+    }
+}
@@ -1,4 +1,6 @@
-use bevy::{ecs::system::SystemParam, prelude::*};
+use super::{FocusGained, FocusLost};
+use crate::prompt::Editable;
+use bevy::{ecs::system::SystemParam, math::CompassQuadrant, prelude::*};
 use bevy_alt_ui_navigation_lite::{
     events::Direction as NavDirection, prelude::*, systems::InputMapping,
 };
@@ -6,8 +8,16 @@ use bevy_alt_ui_navigation_lite::{
 pub(crate) fn plugin(app: &mut App) {
     app.add_event::<BlockRequest>()
         .add_systems(Startup, setup)
-        .add_systems(Update, handle_block_requests.after(NavRequestSystem))
-        .add_plugins(DefaultNavigationPlugins);
+        .add_systems(
+            Update,
+            (handle_block_requests, trigger_focus_changes).after(NavRequestSystem),
+        )
+        .add_plugins((
+            DefaultNavigationPlugins,
+            super::context::plugin,
+            super::scroll::plugin,
+            super::capture::plugin,
+        ));
 }
 
 fn setup(mut input_mapping: ResMut<InputMapping>) {
@@ -16,75 +26,188 @@ fn setup(mut input_mapping: ResMut<InputMapping>) {
 
 pub use bevy_alt_ui_navigation_lite::prelude::Focusable;
 
+/// Trigger [FocusLost] and [FocusGained] as [Focused] moves.
+fn trigger_focus_changes(
+    gained: Query<Entity, Added<Focused>>,
+    mut lost: RemovedComponents<Focused>,
+    mut commands: Commands,
+) {
+    for id in lost.read() {
+        commands.trigger_targets(FocusLost, id);
+    }
+    for id in &gained {
+        commands.trigger_targets(FocusGained, id);
+    }
+}
+
+/// A rudimentary focus parameter
+///
+/// This is only used to test whether an entity is focused.
 #[derive(SystemParam)]
 pub struct Focus<'w, 's> {
-    query: Query<'w, 's, &'static Focused>,
+    query: Query<'w, 's, (), (With<Focused>, With<Focusable>)>,
+    requests: EventWriter<'w, NavRequest>,
 }
 
-impl<'w, 's> Focus<'w, 's> {
+impl Focus<'_, '_> {
+    /// Is entity focused?
     pub fn is_focused(&self, id: Entity) -> bool {
-        self.query.get(id).is_ok()
+        self.query.contains(id)
+    }
+
+    /// Focus on given entity.
+    pub fn focus_on(&mut self, id: Entity) {
+        self.requests.write(NavRequest::FocusOn(id));
+    }
+}
+
+fn to_nav(dir: CompassQuadrant) -> NavDirection {
+    match dir {
+        CompassQuadrant::North => NavDirection::North,
+        CompassQuadrant::South => NavDirection::South,
+        CompassQuadrant::East => NavDirection::East,
+        CompassQuadrant::West => NavDirection::West,
     }
 }
 
+/// A rich focus parameter
+///
+/// Focus moves by [NavRequest], so changes take effect when the navigation
+/// systems next run.
 #[derive(SystemParam)]
 pub struct FocusParam<'w, 's> {
     focus: Query<'w, 's, &'static mut Focusable>,
+    focused: Query<'w, 's, Entity, With<Focused>>,
     requests: EventWriter<'w, NavRequest>,
     blocks: EventWriter<'w, BlockRequest>,
     input_mapping: ResMut<'w, InputMapping>,
 }
 
-#[derive(Event, Debug)]
-struct BlockRequest(Entity);
+#[derive(Event, Debug, Clone, Copy)]
+struct BlockRequest {
+    id: Entity,
+    /// Move focus off the entity even if it is [Editable] and so not blocked
+    and_move: bool,
+}
 
+/// Block requested entities.
+///
+/// A focused entity cannot be blocked, so focus moves off it first and the
+/// block is retried.
 fn handle_block_requests(
     mut blocks: EventReader<BlockRequest>,
-    mut focusables: Query<&mut Focusable>,
+    mut pending: Local<Vec<(BlockRequest, bool)>>,
+    mut focusables: Query<(&mut Focusable, Has<Focused>, Has<Editable>)>,
+    mut requests: EventWriter<NavRequest>,
 ) {
-    for request in blocks.read() {
-        if let Ok(mut focusable) = focusables.get_mut(request.0) {
-            warn!("handle block");
-            if !focusable.block() {
-                warn!("Unable to block focusable. Is it the only one?");
-            }
+    pending.extend(blocks.read().map(|request| (*request, false)));
+    pending.retain_mut(|(request, moved)| {
+        let Ok((mut focusable, focused, editable)) = focusables.get_mut(request.id) else {
+            return false;
+        };
+        if editable && !request.and_move {
+            return false;
         }
-    }
+        if focused && !*moved {
+            requests.write(NavRequest::Move(NavDirection::South));
+            *moved = true;
+            return true;
+        }
+        if !editable && !focusable.block() {
+            warn!("Unable to block focusable. Is it the only one?");
+        }
+        false
+    });
 }
 
-impl<'w, 's> FocusParam<'w, 's> {
+impl FocusParam<'_, '_> {
+    /// Is entity focused?
     pub fn is_focused(&self, id: Entity) -> bool {
         self.focus
             .get(id)
             .map(|focusable| FocusState::Focused == focusable.state())
-            .unwrap_or(true)
+            .unwrap_or(false)
+    }
+
+    /// Move the focus in a direction if possible.
+    pub fn move_focus(&mut self, dir: CompassQuadrant) {
+        self.requests.write(NavRequest::Move(to_nav(dir)));
+    }
+
+    /// Move focus to an entity.
+    pub fn move_focus_to(&mut self, id: Entity) {
+        self.requests.write(NavRequest::FocusOn(id));
     }
 
-    pub fn move_focus_from(&mut self, _id_maybe: impl Into<Option<Entity>>) {
-        self.requests.send(NavRequest::Move(NavDirection::South));
+    /// Move focus away from the current or given entity to the next one.
+    ///
+    /// Does nothing if the given entity is not focused.
+    pub fn move_focus_from(&mut self, id_maybe: impl Into<Option<Entity>>) {
+        if id_maybe.into().is_none_or(|id| self.is_focused(id)) {
+            self.move_focus(CompassQuadrant::South);
+        }
+    }
+
+    /// Move focus to the next entity.
+    ///
+    /// There is no tab order, so this moves down.
+    pub fn move_focus_next(&mut self) {
+        self.move_focus(CompassQuadrant::South);
+    }
+
+    /// Move focus to the previous entity.
+    ///
+    /// There is no tab order, so this moves up.
+    pub fn move_focus_prev(&mut self) {
+        self.move_focus(CompassQuadrant::North);
     }
 
+    /// Is keyboard navigation on?
+    pub fn keyboard_nav(&self) -> bool {
+        self.input_mapping.keyboard_navigation
+    }
+
+    /// Set keyboard navigation.
     pub fn set_keyboard_nav(&mut self, on: bool) {
         self.input_mapping.keyboard_navigation = on;
     }
 
+    /// Block focus on current or given entity and move focus off it.
+    ///
+    /// [Editable] entities are not blocked, but focus still moves.
+    pub fn block_and_move(&mut self, id_maybe: impl Into<Option<Entity>>) {
+        self.request_block(id_maybe.into(), true);
+    }
+
+    /// Is entity blocked?
+    pub fn is_blocked(&self, id: Entity) -> bool {
+        self.focus
+            .get(id)
+            .map(|focusable| FocusState::Blocked == focusable.state())
+            .unwrap_or(true)
+    }
+
+    /// Block focus on current or given entity.
+    ///
+    /// [Editable] entities are not blocked.
     pub fn block(&mut self, id_maybe: impl Into<Option<Entity>>) {
-        if let Some(id) = id_maybe.into() {
-            self.blocks.send(BlockRequest(id));
-            // self.move_focus_from(id);
-            // self.focus.get_mut(id).map(|mut focusable| {
-            //     if !focusable.block() {
-            //         warn!("Unable to block focusable. Is it the only one?");
-            //     }
-            // }).unwrap();
+        self.request_block(id_maybe.into(), false);
+    }
+
+    fn request_block(&mut self, id_maybe: Option<Entity>, and_move: bool) {
+        if let Some(id) = id_maybe.or(self.focused.iter().next()) {
+            self.blocks.write(BlockRequest { id, and_move });
         } else {
-            todo!();
+            warn!("No id to block");
         }
     }
 
-    pub fn block_and_move(&mut self, id_maybe: impl Into<Option<Entity>>) {
-        let id = id_maybe.into();
-        self.move_focus_from(id.clone());
-        self.block(id);
+    /// Unblock focus on current or given entity.
+    pub fn unblock(&mut self, id_maybe: impl Into<Option<Entity>>) {
+        if let Some(id) = id_maybe.into().or(self.focused.iter().next()) {
+            self.focus.get_mut(id).expect("no Focusable").unblock();
+        } else {
+            warn!("No id to unblock");
+        }
     }
 }
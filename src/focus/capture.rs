@@ -0,0 +1,171 @@
+use super::{ContextInput, Focus, Focusable};
+use crate::{string_cursor::StringCursor, AskySet};
+use bevy::{input::InputSystem, platform::collections::HashSet, prelude::*};
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<PromptHasFocus>()
+        .register_type::<InputCapture>()
+        .init_resource::<PromptHasFocus>()
+        .init_resource::<InputCapture>()
+        .add_systems(PreUpdate, update_prompt_has_focus.after(InputSystem))
+        .add_systems(
+            Update,
+            (update_prompt_has_focus, capture_input)
+                .chain()
+                .after(AskySet::Controller),
+        );
+}
+
+/// Is a text prompt, one with a [StringCursor], focused?
+///
+/// Only prompts that take free text count, since they read every key. Those
+/// in a [super::FocusContext] that takes input from a gamepad or a region of
+/// the keyboard do not. Updated after input is read in [PreUpdate] and again
+/// after [AskySet::Controller].
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub struct PromptHasFocus(pub bool);
+
+/// Run condition that is true while a text prompt has focus
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_asky::prelude::*;
+/// # fn walk() {}
+/// App::new().add_systems(Update, walk.run_if(not(prompt_has_focus)));
+/// ```
+pub fn prompt_has_focus(state: Res<PromptHasFocus>) -> bool {
+    state.0
+}
+
+/// Consume the keys typed into a text prompt
+///
+/// When on and [PromptHasFocus], keys just pressed are cleared from
+/// `ButtonInput<KeyCode>` once [AskySet::Controller] has run, and so are
+/// their releases later, so systems ordered after it see no edges for them.
+/// Modifiers and keys held from before are left alone, as is whether a key
+/// is pressed and `KeyboardInput` events; check [prompt_has_focus] for those.
+/// Off by default.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub struct InputCapture(pub bool);
+
+/// Keys never consumed, so shortcuts like `Shift+Tab` keep working
+const MODIFIERS: [KeyCode; 8] = [
+    KeyCode::ShiftLeft,
+    KeyCode::ShiftRight,
+    KeyCode::ControlLeft,
+    KeyCode::ControlRight,
+    KeyCode::AltLeft,
+    KeyCode::AltRight,
+    KeyCode::SuperLeft,
+    KeyCode::SuperRight,
+];
+
+pub(crate) fn update_prompt_has_focus(
+    text: Query<Entity, (With<Focusable>, With<StringCursor>)>,
+    focus: Focus,
+    input: ContextInput,
+    mut state: ResMut<PromptHasFocus>,
+) {
+    let has_focus = text
+        .iter()
+        .any(|id| focus.is_focused(id) && input.typing(id));
+    state.set_if_neq(PromptHasFocus(has_focus));
+}
+
+fn capture_input(
+    capture: Res<InputCapture>,
+    state: Res<PromptHasFocus>,
+    mut keys: ResMut<ButtonInput<KeyCode>>,
+    mut consumed: Local<HashSet<KeyCode>>,
+) {
+    // Hide the release of a key whose press was hidden.
+    let released: Vec<KeyCode> = keys
+        .get_just_released()
+        .filter(|key| consumed.contains(*key))
+        .copied()
+        .collect();
+    for key in released {
+        consumed.remove(&key);
+        keys.clear_just_released(key);
+    }
+    if !(capture.0 && state.0) {
+        return;
+    }
+    let pressed: Vec<KeyCode> = keys
+        .get_just_pressed()
+        .filter(|key| !MODIFIERS.contains(key))
+        .copied()
+        .collect();
+    for key in pressed {
+        consumed.insert(key);
+        keys.clear_just_pressed(key);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        prelude::*,
+        test::{app, press},
+    };
+    use bevy::{ecs::system::RunSystemOnce, input::keyboard::Key};
+
+    /// Make an app with a focused prompt spawned by `spawn`.
+    fn setup(spawn: fn(&mut Commands) -> Entity) -> App {
+        let mut app = app();
+        app.insert_resource(InputCapture(true));
+        let world = app.world_mut();
+        let id = spawn(&mut world.commands());
+        world.flush();
+        world.entity_mut(id).insert(GlobalTransform::default());
+        world
+            .run_system_once(move |mut focus: Focus| focus.focus_on(id))
+            .unwrap();
+        app.update();
+        app
+    }
+
+    #[test]
+    fn capture_text() {
+        let mut app = setup(|commands| commands.construct::<TextField>("Name? ").id());
+        assert!(app.world().resource::<PromptHasFocus>().0);
+        press(app.world_mut(), KeyCode::ShiftLeft, Key::Shift);
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::KeyW);
+        app.update();
+        let keys = app.world().resource::<ButtonInput<KeyCode>>();
+        assert!(!keys.just_pressed(KeyCode::KeyW));
+        assert!(keys.pressed(KeyCode::KeyW));
+        assert!(keys.just_pressed(KeyCode::ShiftLeft));
+
+        // Held keys stay pressed on the next frame.
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .clear();
+        app.update();
+        let keys = app.world().resource::<ButtonInput<KeyCode>>();
+        assert!(keys.pressed(KeyCode::ShiftLeft));
+
+        // The release of a consumed key is hidden too.
+        let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keys.clear();
+        keys.release(KeyCode::KeyW);
+        app.update();
+        let keys = app.world().resource::<ButtonInput<KeyCode>>();
+        assert!(!keys.just_released(KeyCode::KeyW));
+    }
+
+    #[test]
+    fn other_prompts_do_not_capture() {
+        let mut app = setup(|commands| commands.construct::<Confirm>("Ok? ").id());
+        assert!(!app.world().resource::<PromptHasFocus>().0);
+        press(app.world_mut(), KeyCode::KeyW, Key::Character("w".into()));
+        app.update();
+        let keys = app.world().resource::<ButtonInput<KeyCode>>();
+        assert!(keys.just_pressed(KeyCode::KeyW));
+    }
+}
@@ -0,0 +1,320 @@
+use crate::construct::*;
+use bevy::{
+    ecs::system::SystemParam,
+    input::{
+        gamepad::{Gamepad, GamepadButton},
+        keyboard::{Key, KeyboardInput},
+        ButtonState, InputSystem,
+    },
+    platform::collections::HashSet,
+    prelude::*,
+};
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<FocusContext>()
+        .add_systems(PreUpdate, update_context_keys.after(InputSystem));
+}
+
+/// Gamepad buttons and the keys prompts expect for them
+const GAMEPAD_KEYS: [(GamepadButton, KeyCode); 9] = [
+    (GamepadButton::DPadUp, KeyCode::ArrowUp),
+    (GamepadButton::DPadDown, KeyCode::ArrowDown),
+    (GamepadButton::DPadLeft, KeyCode::ArrowLeft),
+    (GamepadButton::DPadRight, KeyCode::ArrowRight),
+    (GamepadButton::South, KeyCode::Enter),
+    (GamepadButton::East, KeyCode::Escape),
+    (GamepadButton::North, KeyCode::Space),
+    (GamepadButton::West, KeyCode::Backspace),
+    (GamepadButton::RightTrigger, KeyCode::Tab),
+];
+
+/// Where a [FocusContext] takes its input from
+#[derive(Debug, Clone, Default, Reflect)]
+pub enum InputSource {
+    /// The whole keyboard, including typed text
+    #[default]
+    Keyboard,
+    /// A region of the keyboard, each key mapped to the key prompts expect,
+    /// e.g., `KeyW` to `ArrowUp`
+    Keys(Vec<(KeyCode, KeyCode)>),
+    /// A gamepad entity. The d-pad maps to the arrow keys, `South` to
+    /// `Enter`, `East` to `Escape`, `North` to `Space`, `West` to `Backspace`
+    /// and `RightTrigger` to `Tab`.
+    Gamepad(Entity),
+}
+
+/// Gives this entity's descendants their own focus and input, e.g., one per
+/// player in local multiplayer
+///
+/// Each prompt belongs to the context of its nearest ancestor with this
+/// component. Prompts outside any context share the global focus and the
+/// whole keyboard. The `focus` feature's backend keeps a single focus, but
+/// input is still routed by context.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_asky::prelude::*;
+/// fn setup(mut commands: Commands, gamepads: Query<Entity, With<Gamepad>>) {
+///     for gamepad in &gamepads {
+///         let form = commands
+///             .spawn((Node::default(), FocusContext::new(InputSource::Gamepad(gamepad))))
+///             .id();
+///         commands.prompt::<Add0<Confirm, color::View>>("Ready? ", Dest::Append(form));
+///     }
+/// }
+/// ```
+#[derive(Component, Debug, Default, Reflect)]
+pub struct FocusContext {
+    /// Where input comes from
+    pub source: InputSource,
+    #[reflect(ignore)]
+    keys: ButtonInput<KeyCode>,
+}
+
+impl FocusContext {
+    /// Make a context with input from `source`.
+    pub fn new(source: InputSource) -> Self {
+        FocusContext {
+            source,
+            keys: ButtonInput::default(),
+        }
+    }
+
+    /// Keys pressed in this context, mapped from its source
+    pub fn keys(&self) -> &ButtonInput<KeyCode> {
+        &self.keys
+    }
+}
+
+impl Construct for FocusContext {
+    type Props = InputSource;
+
+    fn construct(
+        _context: &mut ConstructContext,
+        props: Self::Props,
+    ) -> Result<Self, ConstructError> {
+        Ok(FocusContext::new(props))
+    }
+}
+
+/// Map each context's source to the keys prompts expect.
+pub(crate) fn update_context_keys(
+    mut contexts: Query<&mut FocusContext>,
+    keys: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+) {
+    for mut context in &mut contexts {
+        let context = &mut *context;
+        let down: HashSet<KeyCode> = match &context.source {
+            InputSource::Keyboard => keys.get_pressed().copied().collect(),
+            InputSource::Keys(map) => map
+                .iter()
+                .filter(|(from, _)| keys.pressed(*from))
+                .map(|(_, to)| *to)
+                .collect(),
+            InputSource::Gamepad(id) => gamepads
+                .get(*id)
+                .map(|gamepad| {
+                    GAMEPAD_KEYS
+                        .iter()
+                        .filter(|(button, _)| gamepad.pressed(*button))
+                        .map(|(_, key)| *key)
+                        .collect()
+                })
+                .unwrap_or_default(),
+        };
+        context.keys.clear();
+        let released: Vec<KeyCode> = context
+            .keys
+            .get_pressed()
+            .filter(|key| !down.contains(*key))
+            .copied()
+            .collect();
+        for key in released {
+            context.keys.release(key);
+        }
+        for key in down {
+            context.keys.press(key);
+        }
+    }
+}
+
+/// The logical key prompts expect for a mapped key, if any
+fn logical_key(key: KeyCode) -> Option<Key> {
+    Some(match key {
+        KeyCode::Enter => Key::Enter,
+        KeyCode::Escape => Key::Escape,
+        KeyCode::Backspace => Key::Backspace,
+        KeyCode::Delete => Key::Delete,
+        KeyCode::Space => Key::Space,
+        KeyCode::Tab => Key::Tab,
+        KeyCode::ArrowUp => Key::ArrowUp,
+        KeyCode::ArrowDown => Key::ArrowDown,
+        KeyCode::ArrowLeft => Key::ArrowLeft,
+        KeyCode::ArrowRight => Key::ArrowRight,
+        _ => return None,
+    })
+}
+
+/// Routes input to prompts by their [FocusContext]
+#[derive(SystemParam)]
+pub struct ContextInput<'w, 's> {
+    keys: Res<'w, ButtonInput<KeyCode>>,
+    contexts: Query<'w, 's, &'static FocusContext>,
+    parents: Query<'w, 's, &'static ChildOf>,
+}
+
+impl ContextInput<'_, '_> {
+    /// Return the context entity `id` belongs to, if any.
+    pub fn context_of(&self, id: Entity) -> Option<Entity> {
+        context_of(id, &self.contexts, &self.parents)
+    }
+
+    /// Keys pressed in `id`'s context
+    pub fn keys(&self, id: Entity) -> &ButtonInput<KeyCode> {
+        match self.context_of(id).and_then(|c| self.contexts.get(c).ok()) {
+            Some(context) => &context.keys,
+            None => &self.keys,
+        }
+    }
+
+    /// Does `id`'s context take typed text, i.e., the whole keyboard?
+    pub fn typing(&self, id: Entity) -> bool {
+        self.context_of(id)
+            .and_then(|c| self.contexts.get(c).ok())
+            .is_none_or(|context| matches!(context.source, InputSource::Keyboard))
+    }
+
+    /// Logical keys pressed for `id`, from keyboard `events` if its context
+    /// takes typed text, otherwise from its mapped keys
+    pub fn logical_keys(&self, id: Entity, events: &[KeyboardInput]) -> Vec<Key> {
+        if self.typing(id) {
+            events
+                .iter()
+                .filter(|ev| ev.state == ButtonState::Pressed)
+                .map(|ev| ev.logical_key.clone())
+                .collect()
+        } else {
+            self.keys(id)
+                .get_just_pressed()
+                .filter_map(|key| logical_key(*key))
+                .collect()
+        }
+    }
+}
+
+/// Return the nearest of `id` and its ancestors with a [FocusContext].
+pub(crate) fn context_of<D: bevy::ecs::query::QueryData, F: bevy::ecs::query::QueryFilter>(
+    id: Entity,
+    contexts: &Query<D, F>,
+    parents: &Query<&ChildOf>,
+) -> Option<Entity> {
+    std::iter::once(id)
+        .chain(parents.iter_ancestors(id))
+        .find(|x| contexts.contains(*x))
+}
+
+#[cfg(all(test, not(feature = "focus")))]
+mod test {
+    use super::*;
+    use crate::{
+        prelude::*,
+        test::{app, press},
+    };
+    use bevy::{ecs::system::RunSystemOnce, input::keyboard::Key};
+
+    /// Spawn a context taking `source` with a prompt per `spawn` in a column.
+    fn player(
+        app: &mut App,
+        source: InputSource,
+        spawn: &[fn(&mut Commands) -> Entity],
+    ) -> Vec<Entity> {
+        let world = app.world_mut();
+        let context = world.spawn(FocusContext::new(source)).id();
+        let ids = spawn
+            .iter()
+            .enumerate()
+            .map(|(i, spawn)| {
+                let id = spawn(&mut world.commands());
+                world.flush();
+                // Focus only considers laid out entities.
+                world.entity_mut(id).insert((
+                    ChildOf(context),
+                    GlobalTransform::from_xyz(0.0, 20.0 * i as f32, 0.0),
+                ));
+                id
+            })
+            .collect();
+        ids
+    }
+
+    fn focused(app: &mut App, ids: Vec<Entity>) -> Vec<bool> {
+        app.world_mut()
+            .run_system_once(move |focus: Focus| {
+                ids.iter().map(|id| focus.is_focused(*id)).collect()
+            })
+            .unwrap()
+    }
+
+    fn confirm(commands: &mut Commands) -> Entity {
+        commands.construct::<Confirm>("Ready? ").id()
+    }
+
+    #[test]
+    fn contexts_route_input() {
+        let mut app = app();
+        let players: Vec<Entity> = [KeyCode::KeyE, KeyCode::KeyQ]
+            .into_iter()
+            .flat_map(|escape| {
+                player(
+                    &mut app,
+                    InputSource::Keys(vec![(escape, KeyCode::Escape)]),
+                    &[confirm],
+                )
+            })
+            .collect();
+        let cancels: Vec<_> = players
+            .iter()
+            .map(|id| {
+                let cancels = std::sync::Arc::new(std::sync::Mutex::new(0));
+                let sink = cancels.clone();
+                app.world_mut().entity_mut(*id).observe(
+                    move |mut trigger: Trigger<Submit<bool>>| {
+                        if trigger.event_mut().take_result().is_err() {
+                            *sink.lock().unwrap() += 1;
+                        }
+                    },
+                );
+                cancels
+            })
+            .collect();
+        // Each context focuses its own prompt.
+        app.update();
+        assert_eq!(focused(&mut app, players), [true, true]);
+
+        press(app.world_mut(), KeyCode::KeyQ, Key::Character("q".into()));
+        app.update();
+        assert_eq!(*cancels[0].lock().unwrap(), 0);
+        assert_eq!(*cancels[1].lock().unwrap(), 1);
+    }
+
+    #[test]
+    fn keyboard_nav_per_context() {
+        let mut app = app();
+        player(
+            &mut app,
+            InputSource::Keyboard,
+            &[|commands| commands.construct::<TextField>("Name? ").id()],
+        );
+        let ids = player(
+            &mut app,
+            InputSource::Keys(vec![(KeyCode::KeyS, KeyCode::ArrowDown)]),
+            &[confirm, confirm],
+        );
+        app.update();
+        // A text prompt focused in another context doesn't stop this one.
+        press(app.world_mut(), KeyCode::KeyS, Key::Character("s".into()));
+        app.update();
+        assert_eq!(focused(&mut app, ids), [false, true]);
+    }
+}
@@ -8,3 +8,160 @@ pub use advanced::*;
 mod simple;
 #[cfg(not(feature = "focus"))]
 pub use simple::*;
+#[cfg(not(feature = "focus"))]
+mod nav;
+#[cfg(not(feature = "focus"))]
+pub use nav::{Grid, Linear, NavEdge, NavStrategy, Navigation, NearestInCone};
+
+mod context;
+pub use context::{ContextInput, FocusContext, InputSource};
+mod scroll;
+pub use scroll::ScrollIntoView;
+mod capture;
+pub use capture::{prompt_has_focus, InputCapture, PromptHasFocus};
+
+use bevy::prelude::*;
+
+/// Triggered on an entity when it gains focus
+#[derive(Event, Debug, Clone)]
+pub struct FocusGained;
+
+/// Triggered on an entity when it loses focus
+#[derive(Event, Debug, Clone)]
+pub struct FocusLost;
+
+/// Tests of the API shared by both backends
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::app;
+    use bevy::{ecs::system::RunSystemOnce, math::CompassQuadrant};
+
+    /// Spawn `count` focusables in a column, top to bottom.
+    fn setup(count: usize) -> (App, Vec<Entity>) {
+        let mut app = app();
+        let ids = (0..count)
+            .map(|i| {
+                let y = 20.0 * i as f32;
+                app.world_mut()
+                    .spawn((Focusable::default(), GlobalTransform::from_xyz(0.0, y, 0.0)))
+                    .id()
+            })
+            .collect();
+        app.update();
+        (app, ids)
+    }
+
+    /// Run `f` with the focus parameter, then update so requests take effect.
+    fn run<T: Send + 'static>(
+        app: &mut App,
+        f: impl Fn(&mut FocusParam) -> T + Send + Sync + 'static,
+    ) -> T {
+        let out = app
+            .world_mut()
+            .run_system_once(move |mut focus: FocusParam| f(&mut focus))
+            .unwrap();
+        app.update();
+        out
+    }
+
+    fn focus_on(app: &mut App, id: Entity) {
+        app.world_mut()
+            .run_system_once(move |mut focus: Focus| focus.focus_on(id))
+            .unwrap();
+        app.update();
+    }
+
+    fn focused(app: &mut App, ids: &[Entity]) -> Vec<Entity> {
+        let ids = ids.to_vec();
+        run(app, move |focus| {
+            ids.iter()
+                .copied()
+                .filter(|id| focus.is_focused(*id))
+                .collect()
+        })
+    }
+
+    #[test]
+    fn focus_on_and_move() {
+        let (mut app, ids) = setup(3);
+        focus_on(&mut app, ids[1]);
+        assert_eq!(focused(&mut app, &ids), [ids[1]]);
+        let id = ids[1];
+        assert!(app
+            .world_mut()
+            .run_system_once(move |focus: Focus| focus.is_focused(id))
+            .unwrap());
+
+        run(&mut app, |focus| focus.move_focus(CompassQuadrant::South));
+        assert_eq!(focused(&mut app, &ids), [ids[2]]);
+        let id = ids[0];
+        run(&mut app, move |focus| focus.move_focus_to(id));
+        assert_eq!(focused(&mut app, &ids), [ids[0]]);
+    }
+
+    #[test]
+    fn unfocusable_is_not_focused() {
+        let (mut app, _) = setup(1);
+        let other = app.world_mut().spawn_empty().id();
+        assert!(!run(&mut app, move |focus| focus.is_focused(other)));
+        assert!(run(&mut app, move |focus| focus.is_blocked(other)));
+    }
+
+    #[test]
+    fn block_and_unblock() {
+        let (mut app, ids) = setup(3);
+        focus_on(&mut app, ids[0]);
+        let id = ids[1];
+        run(&mut app, move |focus| focus.block(id));
+        assert!(run(&mut app, move |focus| focus.is_blocked(id)));
+
+        // Blocking the focused entity moves past the blocked one.
+        run(&mut app, |focus| focus.block_and_move(None));
+        assert_eq!(focused(&mut app, &ids), [ids[2]]);
+        let first = ids[0];
+        assert!(run(&mut app, move |focus| focus.is_blocked(first)));
+
+        run(&mut app, move |focus| focus.unblock(id));
+        assert!(!run(&mut app, move |focus| focus.is_blocked(id)));
+
+        // Blocking the focused entity moves focus off it.
+        let last = ids[2];
+        run(&mut app, |focus| focus.block(None));
+        app.update();
+        assert!(run(&mut app, move |focus| focus.is_blocked(last)));
+        assert_eq!(focused(&mut app, &ids), [ids[1]]);
+    }
+
+    #[test]
+    fn keyboard_nav() {
+        let (mut app, _) = setup(1);
+        assert!(run(&mut app, |focus| focus.keyboard_nav()));
+        // Text prompts set this every frame, so check it right away.
+        assert!(!run(&mut app, |focus| {
+            focus.set_keyboard_nav(false);
+            focus.keyboard_nav()
+        }));
+    }
+
+    #[test]
+    fn focus_events() {
+        let (mut app, ids) = setup(2);
+        focus_on(&mut app, ids[0]);
+        #[derive(Resource, Default)]
+        struct Log(Vec<(&'static str, Entity)>);
+        let world = app.world_mut();
+        world.init_resource::<Log>();
+        world.add_observer(|trigger: Trigger<FocusGained>, mut log: ResMut<Log>| {
+            log.0.push(("gained", trigger.target()));
+        });
+        world.add_observer(|trigger: Trigger<FocusLost>, mut log: ResMut<Log>| {
+            log.0.push(("lost", trigger.target()));
+        });
+        focus_on(&mut app, ids[1]);
+        assert_eq!(
+            app.world().resource::<Log>().0,
+            [("lost", ids[0]), ("gained", ids[1])]
+        );
+    }
+}
@@ -0,0 +1,322 @@
+//! Directional navigation strategies
+use bevy::prelude::*;
+use std::fmt::Debug;
+
+/// Chooses where directional navigation moves focus
+///
+/// `dir` is in UI coordinates, so [Dir2::Y] points down. `candidates` hold
+/// the positions of the entities that may be focused, excluding the one at
+/// `from`.
+pub trait NavStrategy: Send + Sync + 'static {
+    /// Return the candidate to move to in direction `dir`, if any.
+    fn next(&self, dir: Dir2, from: Vec2, candidates: &[(Entity, Vec2)]) -> Option<Entity>;
+
+    /// Return the candidate to wrap around to when [NavStrategy::next] finds
+    /// none.
+    fn wrap(&self, dir: Dir2, from: Vec2, candidates: &[(Entity, Vec2)]) -> Option<Entity>;
+}
+
+/// Moves to the nearest candidate projected onto the direction
+///
+/// Cheap and fine for a single row or column, but in several columns it may
+/// jump to another row that is nearer along the direction.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Linear;
+
+impl NavStrategy for Linear {
+    fn next(&self, dir: Dir2, from: Vec2, candidates: &[(Entity, Vec2)]) -> Option<Entity> {
+        focus_next(dir, from, candidates.iter().copied()).map(|x| x.0)
+    }
+
+    fn wrap(&self, dir: Dir2, from: Vec2, candidates: &[(Entity, Vec2)]) -> Option<Entity> {
+        focus_next_rev(-dir, from, candidates.iter().copied()).map(|x| x.0)
+    }
+}
+
+/// Moves to the nearest candidate within a cone around the direction,
+/// penalizing distance off its axis
+#[derive(Debug, Clone, Copy)]
+pub struct NearestInCone {
+    /// Half the angle of the cone in radians
+    pub half_angle: f32,
+}
+
+impl Default for NearestInCone {
+    fn default() -> Self {
+        NearestInCone {
+            half_angle: std::f32::consts::FRAC_PI_4,
+        }
+    }
+}
+
+impl NearestInCone {
+    /// Return how far along and off axis of `dir` each candidate in the cone is.
+    fn in_cone<'a>(
+        &self,
+        dir: Dir2,
+        from: Vec2,
+        candidates: &'a [(Entity, Vec2)],
+    ) -> impl Iterator<Item = (Entity, f32, f32)> + 'a {
+        let slope = self.half_angle.tan();
+        candidates.iter().filter_map(move |(id, pos)| {
+            let delta = *pos - from;
+            let along = delta.dot(*dir);
+            let off = delta.perp_dot(*dir).abs();
+            (along > 0.0 && off <= along * slope).then_some((*id, along, off))
+        })
+    }
+}
+
+impl NavStrategy for NearestInCone {
+    fn next(&self, dir: Dir2, from: Vec2, candidates: &[(Entity, Vec2)]) -> Option<Entity> {
+        self.in_cone(dir, from, candidates)
+            .min_by(|a, b| (a.1 + 2.0 * a.2).total_cmp(&(b.1 + 2.0 * b.2)))
+            .map(|x| x.0)
+    }
+
+    fn wrap(&self, dir: Dir2, from: Vec2, candidates: &[(Entity, Vec2)]) -> Option<Entity> {
+        self.in_cone(-dir, from, candidates)
+            .max_by(|a, b| (a.1 - 2.0 * a.2).total_cmp(&(b.1 - 2.0 * b.2)))
+            .map(|x| x.0)
+    }
+}
+
+/// Moves along the row or column focus is on, or else to the nearest
+/// candidate in the next row or column
+#[derive(Debug, Clone, Copy)]
+pub struct Grid {
+    /// How far off in pixels a candidate may be and still share a row or
+    /// column
+    pub tolerance: f32,
+}
+
+impl Default for Grid {
+    fn default() -> Self {
+        Grid { tolerance: 8.0 }
+    }
+}
+
+impl Grid {
+    /// Return how far along and off axis of `dir` each candidate ahead is.
+    fn ahead<'a>(
+        &self,
+        dir: Dir2,
+        from: Vec2,
+        candidates: &'a [(Entity, Vec2)],
+    ) -> impl Iterator<Item = (Entity, f32, f32)> + 'a {
+        let tolerance = self.tolerance;
+        candidates.iter().filter_map(move |(id, pos)| {
+            let delta = *pos - from;
+            let along = delta.dot(*dir);
+            (along > tolerance).then_some((*id, along, delta.perp_dot(*dir).abs()))
+        })
+    }
+}
+
+impl NavStrategy for Grid {
+    fn next(&self, dir: Dir2, from: Vec2, candidates: &[(Entity, Vec2)]) -> Option<Entity> {
+        let in_line = self
+            .ahead(dir, from, candidates)
+            .filter(|x| x.2 <= self.tolerance)
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        in_line
+            .or_else(|| {
+                let nearest = self
+                    .ahead(dir, from, candidates)
+                    .map(|x| x.1)
+                    .min_by(f32::total_cmp)?;
+                self.ahead(dir, from, candidates)
+                    .filter(|x| x.1 <= nearest + self.tolerance)
+                    .min_by(|a, b| a.2.total_cmp(&b.2))
+            })
+            .map(|x| x.0)
+    }
+
+    fn wrap(&self, dir: Dir2, from: Vec2, candidates: &[(Entity, Vec2)]) -> Option<Entity> {
+        self.ahead(-dir, from, candidates)
+            .filter(|x| x.2 <= self.tolerance)
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|x| x.0)
+    }
+}
+
+/// What directional navigation does at the edge of a group
+///
+/// Add it to an entity to make its descendants a group: focus moves within
+/// the group first. Without one, [Navigation::edge] applies.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+pub enum NavEdge {
+    /// Wrap around to the other side
+    #[default]
+    Wrap,
+    /// Stay put
+    Stop,
+    /// Move on to the enclosing group
+    Escape,
+}
+
+/// How directional navigation moves focus
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_asky::prelude::*;
+/// fn setup(mut navigation: ResMut<Navigation>) {
+///     navigation.strategy = Box::new(Grid::default());
+///     navigation.edge = NavEdge::Stop;
+/// }
+/// ```
+#[derive(Resource)]
+pub struct Navigation {
+    /// Chooses the next entity
+    pub strategy: Box<dyn NavStrategy>,
+    /// What to do at the edge outside any [NavEdge] group
+    pub edge: NavEdge,
+}
+
+impl Default for Navigation {
+    fn default() -> Self {
+        Navigation {
+            strategy: Box::new(Linear),
+            edge: NavEdge::Wrap,
+        }
+    }
+}
+
+fn focus_next_rev<T>(
+    dir: Dir2,
+    curr_pos: Vec2,
+    elements: impl Iterator<Item = (T, Vec2)>,
+) -> Option<(T, f32)>
+where
+    T: PartialEq + Copy + Debug,
+{
+    elements
+        .filter_map(|(id, pos)| {
+            let delta = pos - curr_pos;
+            let dirdist = delta.dot(*dir);
+            (dirdist > 0.0).then_some((id, dirdist))
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+fn focus_next<T>(
+    dir: Dir2,
+    curr_pos: Vec2,
+    elements: impl Iterator<Item = (T, Vec2)>,
+) -> Option<(T, f32)>
+where
+    T: PartialEq + Copy + Debug,
+{
+    elements
+        .filter_map(|(id, pos)| {
+            let delta = pos - curr_pos;
+            let dirdist = delta.dot(*dir);
+            (dirdist > 0.0).then_some((id, dirdist))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Move east from the first element, wrapping, and return the index of
+    /// the element focused.
+    fn east(strategy: &impl NavStrategy, elements: &[Vec2]) -> Option<u32> {
+        let candidates: Vec<(Entity, Vec2)> = elements
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, pos)| (Entity::from_raw(i as u32), *pos))
+            .collect();
+        strategy
+            .next(Dir2::X, elements[0], &candidates)
+            .or_else(|| strategy.wrap(Dir2::X, elements[0], &candidates))
+            .map(|id| id.index())
+    }
+
+    #[test]
+    fn next_right() {
+        let elements = [(0, Vec2::ZERO), (1, Vec2::X)];
+        assert_eq!(
+            focus_next(Dir2::X, elements[0].1, elements[1..].iter().copied()),
+            Some((1, 1.0))
+        );
+    }
+
+    #[test]
+    fn two_right() {
+        let elements = [(0, Vec2::ZERO), (1, Vec2::X), (2, 2.0 * Vec2::X)];
+        assert_eq!(
+            focus_next(Dir2::X, elements[0].1, elements[1..].iter().copied()),
+            Some((1, 1.0))
+        );
+    }
+
+    #[test]
+    fn none_right() {
+        let elements = [(0, Vec2::ZERO), (1, Vec2::NEG_X)];
+        assert_eq!(
+            focus_next(Dir2::X, elements[0].1, elements[1..].iter().copied()),
+            None
+        );
+    }
+
+    #[test]
+    fn none_right_wrap() {
+        assert_eq!(east(&Linear, &[Vec2::ZERO, Vec2::NEG_X]), Some(1));
+    }
+
+    #[test]
+    fn two_left_wrap() {
+        assert_eq!(
+            east(&Linear, &[Vec2::ZERO, Vec2::NEG_X, 2.0 * Vec2::NEG_X]),
+            Some(2)
+        );
+    }
+
+    /// A row of checkboxes above a column of them
+    const CHECKBOX_GROUP: [Vec2; 6] = [
+        Vec2::new(258.0, 12.0),
+        Vec2::new(384.0, 12.0),
+        Vec2::new(510.0, 12.0),
+        Vec2::new(288.0, 60.0),
+        Vec2::new(288.0, 84.0),
+        Vec2::new(288.0, 108.0),
+    ];
+
+    #[test]
+    fn checkbox_group() {
+        // Linear jumps down a row, to the nearer x.
+        assert_eq!(east(&Linear, &CHECKBOX_GROUP), Some(3));
+        assert_eq!(east(&NearestInCone::default(), &CHECKBOX_GROUP), Some(1));
+        assert_eq!(east(&Grid::default(), &CHECKBOX_GROUP), Some(1));
+    }
+
+    #[test]
+    fn wrap_along_row() {
+        let row = [
+            Vec2::new(510.0, 12.0),
+            Vec2::new(258.0, 12.0),
+            Vec2::new(384.0, 12.0),
+        ];
+        assert_eq!(east(&NearestInCone::default(), &row), Some(1));
+        assert_eq!(east(&Grid::default(), &row), Some(1));
+    }
+
+    #[test]
+    fn grid_next_row() {
+        let grid = Grid::default();
+        let candidates: Vec<(Entity, Vec2)> = CHECKBOX_GROUP[1..]
+            .iter()
+            .enumerate()
+            .map(|(i, pos)| (Entity::from_raw(i as u32 + 1), *pos))
+            .collect();
+        // Down from the first checkbox, not quite in its column.
+        assert_eq!(
+            grid.next(Dir2::Y, CHECKBOX_GROUP[0], &candidates)
+                .map(|id| id.index()),
+            Some(3)
+        );
+    }
+}
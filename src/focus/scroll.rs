@@ -0,0 +1,189 @@
+use super::FocusGained;
+use bevy::{prelude::*, ui::UiSystem};
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<ScrollIntoView>()
+        .init_resource::<ScrollIntoView>()
+        .init_resource::<ScrollTargets>()
+        .add_observer(focus_gained)
+        .add_systems(
+            PostUpdate,
+            (scroll_into_view, smooth_scroll)
+                .chain()
+                .before(UiSystem::Layout),
+        );
+}
+
+/// Scrolls the nearest scrolling ancestor of a newly focused node so that it
+/// is fully visible
+///
+/// An ancestor scrolls if its [Node::overflow] is [OverflowAxis::Scroll] on
+/// that axis.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_asky::prelude::*;
+/// fn setup(mut scroll: ResMut<ScrollIntoView>) {
+///     scroll.margin = 16.0;
+///     scroll.smooth = Some(12.0);
+/// }
+/// ```
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct ScrollIntoView {
+    /// Scroll when focus changes
+    pub enabled: bool,
+    /// Space kept between the focused node and the container's edges in
+    /// logical pixels
+    pub margin: f32,
+    /// Animate the scroll at this rate per second, or jump if `None`
+    pub smooth: Option<f32>,
+}
+
+impl Default for ScrollIntoView {
+    fn default() -> Self {
+        ScrollIntoView {
+            enabled: true,
+            margin: 8.0,
+            smooth: None,
+        }
+    }
+}
+
+/// Focused nodes to scroll into view once they are laid out
+#[derive(Resource, Default)]
+struct ScrollTargets(Vec<Entity>);
+
+/// An animated scroll in logical pixels
+#[derive(Component, Debug)]
+struct ScrollGoal {
+    to: Vec2,
+    /// Where the animation last left the scroll; anything else moving it
+    /// stops the animation.
+    last: Vec2,
+}
+
+fn focus_gained(
+    trigger: Trigger<FocusGained>,
+    settings: Res<ScrollIntoView>,
+    mut targets: ResMut<ScrollTargets>,
+) {
+    if settings.enabled {
+        targets.0.push(trigger.target());
+    }
+}
+
+/// Return the distance to scroll on one axis so that `inner` lies within
+/// `outer` less `margin`, preferring its start if it does not fit.
+fn overlap(inner: (f32, f32), outer: (f32, f32), margin: f32) -> f32 {
+    let (start, end) = (inner.0 - margin, inner.1 + margin);
+    if start < outer.0 || end - start > outer.1 - outer.0 {
+        start - outer.0
+    } else if end > outer.1 {
+        end - outer.1
+    } else {
+        0.0
+    }
+}
+
+fn scroll_into_view(
+    mut targets: ResMut<ScrollTargets>,
+    settings: Res<ScrollIntoView>,
+    nodes: Query<(&Node, &ComputedNode, &GlobalTransform)>,
+    parents: Query<&ChildOf>,
+    mut positions: Query<&mut ScrollPosition>,
+    mut commands: Commands,
+) {
+    targets.0.retain(|id| {
+        let Ok((_, node, transform)) = nodes.get(*id) else {
+            // Not a UI node; nothing to scroll.
+            return false;
+        };
+        if node.size() == Vec2::ZERO {
+            // Not laid out yet.
+            return true;
+        }
+        let Some((container, overflow, outer, outer_transform)) =
+            parents.iter_ancestors(*id).find_map(|ancestor| {
+                let (style, node, transform) = nodes.get(ancestor).ok()?;
+                let overflow = style.overflow;
+                (overflow.x == OverflowAxis::Scroll || overflow.y == OverflowAxis::Scroll)
+                    .then_some((ancestor, overflow, node, transform))
+            })
+        else {
+            return false;
+        };
+        let Ok(mut position) = positions.get_mut(container) else {
+            return false;
+        };
+        // Layout is in physical pixels; scroll positions are logical.
+        let scale = outer.inverse_scale_factor();
+        let margin = settings.margin / scale;
+        let inner = Rect::from_center_size(transform.translation().truncate(), node.size());
+        let outer = Rect::from_center_size(outer_transform.translation().truncate(), outer.size());
+        let mut delta = Vec2::ZERO;
+        if overflow.x == OverflowAxis::Scroll {
+            delta.x = overlap(
+                (inner.min.x, inner.max.x),
+                (outer.min.x, outer.max.x),
+                margin,
+            );
+        }
+        if overflow.y == OverflowAxis::Scroll {
+            delta.y = overlap(
+                (inner.min.y, inner.max.y),
+                (outer.min.y, outer.max.y),
+                margin,
+            );
+        }
+        let from = Vec2::new(position.offset_x, position.offset_y);
+        let to = (from + delta * scale).max(Vec2::ZERO);
+        if settings.smooth.is_some() {
+            commands
+                .entity(container)
+                .insert(ScrollGoal { to, last: from });
+        } else {
+            position.offset_x = to.x;
+            position.offset_y = to.y;
+        }
+        false
+    });
+}
+
+/// Move animated scrolls toward their goals.
+fn smooth_scroll(
+    mut query: Query<(Entity, &mut ScrollPosition, &mut ScrollGoal)>,
+    settings: Res<ScrollIntoView>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    let rate = settings.smooth.unwrap_or(f32::INFINITY);
+    let t = 1.0 - (-rate * time.delta_secs()).exp();
+    for (id, mut position, mut goal) in &mut query {
+        let current = Vec2::new(position.offset_x, position.offset_y);
+        if current.distance(goal.last) > 0.5 || current.distance(goal.to) < 0.5 {
+            // Arrived, or clamped by layout or scrolled by someone else.
+            commands.entity(id).remove::<ScrollGoal>();
+            continue;
+        }
+        let next = current.lerp(goal.to, t);
+        position.offset_x = next.x;
+        position.offset_y = next.y;
+        goal.last = next;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn overlap_edges() {
+        let outer = (0.0, 100.0);
+        assert_eq!(overlap((10.0, 20.0), outer, 5.0), 0.0);
+        assert_eq!(overlap((90.0, 110.0), outer, 5.0), 15.0);
+        assert_eq!(overlap((-30.0, -10.0), outer, 5.0), -35.0);
+        // Too tall to fit: show its start.
+        assert_eq!(overlap((50.0, 200.0), outer, 0.0), 50.0);
+    }
+}
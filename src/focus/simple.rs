@@ -1,42 +1,238 @@
-use bevy::{ecs::system::SystemParam, math::CompassQuadrant, prelude::*};
-use std::fmt::Debug;
+use super::{context::context_of, FocusContext, FocusGained, FocusLost, NavEdge, Navigation};
+use crate::{construct::*, prompt::Editable, string_cursor::StringCursor, AskySet};
+use bevy::{
+    ecs::{component::HookContext, system::SystemParam, world::DeferredWorld},
+    math::CompassQuadrant,
+    prelude::*,
+};
+#[cfg(feature = "input_focus")]
+use bevy::{input_focus::directional_navigation::DirectionalNavigationMap, math::CompassOctant};
+use private::FocusExt;
+
+/// Edges added by other UI for arrow keys to follow
+#[cfg(feature = "input_focus")]
+type NavMap<'w> = Option<Res<'w, DirectionalNavigationMap>>;
+#[cfg(not(feature = "input_focus"))]
+type NavMap<'w> = std::marker::PhantomData<&'w ()>;
+
+/// Return the neighbor of `id` toward `dir` from the [NavMap], if any.
+#[cfg(feature = "input_focus")]
+fn nav_neighbor(map: &NavMap, id: Entity, dir: CompassQuadrant) -> Option<Entity> {
+    let octant = match dir {
+        CompassQuadrant::North => CompassOctant::North,
+        CompassQuadrant::East => CompassOctant::East,
+        CompassQuadrant::South => CompassOctant::South,
+        CompassQuadrant::West => CompassOctant::West,
+    };
+    map.as_ref()?.get_neighbor(id, octant)
+}
+
+#[cfg(not(feature = "input_focus"))]
+fn nav_neighbor(_map: &NavMap, _id: Entity, _dir: CompassQuadrant) -> Option<Entity> {
+    None
+}
 
 mod private {
-    use bevy::prelude::*;
+    use bevy::{ecs::entity::hash_map::EntityHashMap, prelude::*};
 
     /// A substitute for [bevy::a11y::Focus]
     ///
     /// [bevy::a11y::Focus] caused panics for reasons I didn't understand so I
-    /// used my own instead, but I'd like to switch to Bevy's eventually.
+    /// used my own instead. The `input_focus` feature uses Bevy's `InputFocus`.
+    #[cfg(not(feature = "input_focus"))]
     #[derive(Resource, Default, Debug, Reflect)]
     #[reflect(Resource)]
     pub struct Focus(pub Option<Entity>);
 
-    impl Focus {
-        /// Is entity focused?
-        pub fn is_focused(&self, id: Entity) -> bool {
-            self.0.map(|f| f == id).unwrap_or(false)
+    #[cfg(feature = "input_focus")]
+    pub use bevy::input_focus::InputFocus as Focus;
+
+    /// Methods on whichever focus resource is in use
+    pub trait FocusExt {
+        /// Focus on `next`.
+        fn change(&mut self, next: Option<Entity>, commands: &mut Commands);
+    }
+
+    impl FocusExt for Focus {
+        fn change(&mut self, next: Option<Entity>, commands: &mut Commands) {
+            let old = std::mem::replace(&mut self.0, next);
+            super::focus_changed(old, next, commands);
         }
     }
+
+    /// Focus as of the last [FocusGained](crate::focus::FocusGained)
+    ///
+    /// Other UI may change `InputFocus`, so its changes are announced by
+    /// comparing against this.
+    #[cfg(feature = "input_focus")]
+    #[derive(Resource, Default, Debug)]
+    pub struct Announced(pub Option<Entity>);
+
+    /// Focus of each [FocusContext](crate::focus::FocusContext)
+    #[derive(Resource, Default, Debug)]
+    pub struct ContextFocus(pub EntityHashMap<Entity>);
+
+    /// Open [super::FocusScope]s, innermost last, with the focus before each
+    /// one opened
+    #[derive(Resource, Default, Debug)]
+    pub struct FocusScopes(pub Vec<(Entity, Option<Entity>)>);
 }
 
 /// A rudimentary focus parameter
 ///
 /// This is only used to test whether an entity is focused.
 #[derive(SystemParam)]
-pub struct Focus<'w> {
+pub struct Focus<'w, 's> {
     focus: ResMut<'w, private::Focus>,
+    context_focus: ResMut<'w, private::ContextFocus>,
+    contexts: Query<'w, 's, (), With<FocusContext>>,
+    parents: Query<'w, 's, &'static ChildOf>,
+    commands: Commands<'w, 's>,
 }
 
-impl Focus<'_> {
+impl Focus<'_, '_> {
     /// Is entity focused?
     pub fn is_focused(&self, id: Entity) -> bool {
-        self.focus.is_focused(id)
+        let context = context_of(id, &self.contexts, &self.parents);
+        focused_in(&self.focus, &self.context_focus, context) == Some(id)
     }
 
     /// Focus on given entity.
     pub fn focus_on(&mut self, id: Entity) {
-        self.focus.0 = Some(id);
+        let context = context_of(id, &self.contexts, &self.parents);
+        set_focus_in(
+            &mut self.focus,
+            &mut self.context_focus,
+            context,
+            Some(id),
+            &mut self.commands,
+        );
+    }
+}
+
+/// Return the focus of a [FocusContext], or the global focus for `None`.
+fn focused_in(
+    focus: &private::Focus,
+    context_focus: &private::ContextFocus,
+    context: Option<Entity>,
+) -> Option<Entity> {
+    match context {
+        Some(context) => context_focus.0.get(&context).copied(),
+        None => focus.0,
+    }
+}
+
+/// Set the focus of a [FocusContext], or the global focus for `None`.
+fn set_focus_in(
+    focus: &mut private::Focus,
+    context_focus: &mut private::ContextFocus,
+    context: Option<Entity>,
+    next: Option<Entity>,
+    commands: &mut Commands,
+) {
+    let Some(context) = context else {
+        focus.change(next, commands);
+        return;
+    };
+    let old = match next {
+        Some(next) => context_focus.0.insert(context, next),
+        None => context_focus.0.remove(&context),
+    };
+    // Only the global focus may be shared with other UI, so announce now.
+    trigger_focus_change(old, next, commands);
+}
+
+/// Orders focus for `Tab` and `Shift+Tab`
+///
+/// Focusables are visited in ascending order, then those without a tab index,
+/// with ties in entity order. Arrow keys also follow this order from an
+/// entity with a tab index instead of its layout. Tabbing skips a negative
+/// index, but the entity may still be focused otherwise. Entities with a tab
+/// index but no [Focusable], e.g., widgets from other UI, are tabbed to also.
+#[cfg(not(feature = "input_focus"))]
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect)]
+pub struct TabIndex(pub i32);
+
+#[cfg(feature = "input_focus")]
+pub use bevy::input_focus::tab_navigation::TabIndex;
+
+impl Construct for TabIndex {
+    type Props = i32;
+
+    fn construct(
+        _context: &mut ConstructContext,
+        props: Self::Props,
+    ) -> Result<Self, ConstructError> {
+        Ok(TabIndex(props))
+    }
+}
+
+/// Confines focus to the descendants of this entity, e.g., a modal dialog
+///
+/// Focus moves into the scope when it is added. Scopes nest; only the last
+/// one added is active. When it is removed or despawned, any scopes added
+/// after it close too, and focus returns to where it was before it opened.
+#[derive(Component, Debug, Default, Reflect)]
+#[component(on_add = open_scope, on_remove = close_scope)]
+pub struct FocusScope;
+
+impl Construct for FocusScope {
+    type Props = ();
+
+    fn construct(
+        _context: &mut ConstructContext,
+        _props: Self::Props,
+    ) -> Result<Self, ConstructError> {
+        Ok(FocusScope)
+    }
+}
+
+fn open_scope(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+    let Some(mut focus) = world.get_resource_mut::<private::Focus>() else {
+        return;
+    };
+    // Focus moves to the first entity in scope when reset.
+    let previous = focus.0.take();
+    focus_changed(previous, None, &mut world.commands());
+    if let Some(mut scopes) = world.get_resource_mut::<private::FocusScopes>() {
+        scopes.0.push((entity, previous));
+    }
+}
+
+fn close_scope(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+    let Some(mut scopes) = world.get_resource_mut::<private::FocusScopes>() else {
+        return;
+    };
+    let Some(i) = scopes.0.iter().position(|(scope, _)| *scope == entity) else {
+        return;
+    };
+    // Scopes opened after it close with it.
+    let previous = scopes.0[i].1;
+    scopes.0.truncate(i);
+    let old = std::mem::replace(&mut world.resource_mut::<private::Focus>().0, previous);
+    focus_changed(old, previous, &mut world.commands());
+}
+
+/// Announce that asky moved focus from `old` to `next`.
+///
+/// With the `input_focus` feature, `announce_focus` does this instead, so
+/// that changes made by other UI are announced too.
+fn focus_changed(old: Option<Entity>, next: Option<Entity>, commands: &mut Commands) {
+    if cfg!(not(feature = "input_focus")) {
+        trigger_focus_change(old, next, commands);
+    }
+}
+
+/// Trigger [FocusLost] and [FocusGained] if focus moved from `old` to `next`.
+fn trigger_focus_change(old: Option<Entity>, next: Option<Entity>, commands: &mut Commands) {
+    if old == next {
+        return;
+    }
+    if let Some(old) = old {
+        commands.trigger_targets(FocusLost, old);
+    }
+    if let Some(next) = next {
+        commands.trigger_targets(FocusGained, next);
     }
 }
 
@@ -45,31 +241,69 @@ impl Focus<'_> {
 pub struct KeyboardNav(bool);
 
 /// Marker for Focusable components
+///
+/// Observe [FocusGained] and [FocusLost] to learn when its focus changes.
 #[derive(Component, Clone, Default, Reflect)]
 pub struct Focusable {
-    version: usize,
     block: bool,
 }
 
-impl Focusable {
-    /// Modify the focusable.
-    ///
-    /// Useful for view that can filter by `Changed<Focusable>`.
-    fn touch(&mut self) {
-        self.version += 1;
-    }
-}
-
 // #[derive(Component, Default, Debug)]
 // pub struct Blocked;
 
 pub(crate) fn plugin(app: &mut App) {
     app.register_type::<private::Focus>()
         .register_type::<Focusable>()
-        .insert_resource(private::Focus(None))
+        .register_type::<TabIndex>()
+        .init_resource::<private::Focus>()
+        .init_resource::<private::FocusScopes>()
+        .init_resource::<private::ContextFocus>()
+        .register_type::<FocusScope>()
+        .register_type::<NavEdge>()
+        .init_resource::<Navigation>()
         .insert_resource(KeyboardNav(true))
-        .add_systems(PreUpdate, focus_keys)
-        .add_systems(Update, reset_focus);
+        .add_plugins((
+            super::context::plugin,
+            super::scroll::plugin,
+            super::capture::plugin,
+        ))
+        .add_systems(
+            PreUpdate,
+            (focus_keys, focus_on_tab)
+                .after(super::context::update_context_keys)
+                .before(super::capture::update_prompt_has_focus),
+        )
+        // Restore focus once controllers have moved it this frame.
+        .add_systems(Update, reset_focus.after(AskySet::Controller));
+
+    #[cfg(feature = "input_focus")]
+    app.init_resource::<private::Announced>()
+        .init_resource::<DirectionalNavigationMap>()
+        .add_systems(
+            PreUpdate,
+            announce_focus.after(focus_keys).after(focus_on_tab),
+        )
+        .add_systems(
+            Update,
+            announce_focus
+                .after(AskySet::Controller)
+                .after(reset_focus)
+                .before(AskySet::View),
+        );
+}
+
+/// Trigger [FocusLost] and [FocusGained] when [InputFocus] changes, whether
+/// asky or other UI changed it.
+#[cfg(feature = "input_focus")]
+fn announce_focus(
+    focus: Res<private::Focus>,
+    mut announced: ResMut<private::Announced>,
+    mut commands: Commands,
+) {
+    if announced.0 != focus.0 {
+        let old = std::mem::replace(&mut announced.0, focus.0);
+        trigger_focus_change(old, focus.0, &mut commands);
+    }
 }
 
 fn to_dir(dir: CompassQuadrant) -> Dir2 {
@@ -91,88 +325,200 @@ pub struct FocusParam<'w, 's> {
     // nodes: Query<'w, 's, (Entity, &'static Node)>,
     focus: ResMut<'w, private::Focus>,
     keyboard_nav: ResMut<'w, KeyboardNav>,
+    editable: Query<'w, 's, (), With<Editable>>,
+    tab_index: Query<'w, 's, &'static TabIndex>,
+    others: Query<'w, 's, (Entity, &'static TabIndex), Without<Focusable>>,
+    nav_map: NavMap<'w>,
+    parents: Query<'w, 's, &'static ChildOf>,
+    scopes: Res<'w, private::FocusScopes>,
+    navigation: Res<'w, Navigation>,
+    nav_edges: Query<'w, 's, &'static NavEdge>,
+    contexts: Query<'w, 's, Entity, With<FocusContext>>,
+    context_focus: ResMut<'w, private::ContextFocus>,
+    commands: Commands<'w, 's>,
 }
 
 impl FocusParam<'_, '_> {
     /// Is entity focused?
     pub fn is_focused(&self, id: Entity) -> bool {
-        self.focus.is_focused(id)
+        self.focused_in(self.context_of(id)) == Some(id)
+    }
+
+    /// Return the [FocusContext] entity `id` belongs to, if any.
+    pub fn context_of(&self, id: Entity) -> Option<Entity> {
+        context_of(id, &self.contexts, &self.parents)
+    }
+
+    /// Return the focus of a [FocusContext], or the global focus for `None`.
+    pub fn focused_in(&self, context: Option<Entity>) -> Option<Entity> {
+        focused_in(&self.focus, &self.context_focus, context)
     }
 
-    /// Move the focus in a direction if possible.
+    fn set_focus_in(&mut self, context: Option<Entity>, next: Option<Entity>) {
+        set_focus_in(
+            &mut self.focus,
+            &mut self.context_focus,
+            context,
+            next,
+            &mut self.commands,
+        );
+    }
+
+    /// Is entity within the active [FocusScope], if any?
+    pub fn in_scope(&self, id: Entity) -> bool {
+        self.scopes.0.last().is_none_or(|(scope, _)| {
+            id == *scope || self.parents.iter_ancestors(id).any(|x| x == *scope)
+        })
+    }
+
+    /// Move the global focus in a direction if possible.
+    ///
+    /// Edges in Bevy's `DirectionalNavigationMap` are followed first with the
+    /// `input_focus` feature.
     pub fn move_focus(&mut self, dir: CompassQuadrant) {
-        let (old_id, old_pos) = if let Some(old_focus) = self.focus.0 {
-            if let Ok((id, _, transform)) = self.query.get_mut(old_focus) {
-                (id, transform.translation())
-            } else {
-                self.move_focus_from(None);
-                return;
-            }
-        } else {
-            self.move_focus_from(None);
+        self.move_focus_in(None, dir);
+    }
+
+    /// Move the focus of a [FocusContext], or the global focus for `None`,
+    /// in a direction if possible.
+    pub fn move_focus_in(&mut self, context: Option<Entity>, dir: CompassQuadrant) {
+        let old_focus = self.focused_in(context);
+        if let Some(id) = old_focus.and_then(|old| nav_neighbor(&self.nav_map, old, dir)) {
+            self.move_focus_to(id);
             return;
-        };
-        let dir: Dir2 = to_dir(dir);
-        if let Some((min_id, _min_dist)) = focus_next_wrap(dir, (old_id, old_pos.xy()), || {
+        }
+        let Some((old_id, old_pos)) = old_focus.and_then(|old_focus| {
             self.query
+                .get(old_focus)
+                .ok()
+                .map(|(id, _, transform)| (id, transform.translation()))
+        }) else {
+            let next = self.tab_from(context, None, true);
+            self.set_focus_in(context, next);
+            return;
+        };
+        if self.tab_index.contains(old_id) {
+            // Explicit order overrides the layout.
+            use CompassQuadrant::*;
+            if let Some(id) = self.tab_from(context, Some(old_id), matches!(dir, South | East)) {
+                self.move_focus_to(id);
+            }
+            return;
+        }
+        let candidates: Vec<(Entity, Vec2)> = self
+            .query
+            .iter()
+            .filter(|(id, _, _)| {
+                *id != old_id && self.in_scope(*id) && self.context_of(*id) == context
+            })
+            .map(|(id, _, transform)| (id, transform.translation().xy()))
+            .collect();
+        if let Some(id) = self.nav_next(to_dir(dir), (old_id, old_pos.xy()), &candidates) {
+            self.move_focus_to(id);
+        }
+    }
+
+    /// Return where to move from `from` in direction `dir`, trying its
+    /// innermost [NavEdge] group first.
+    fn nav_next(
+        &self,
+        dir: Dir2,
+        from: (Entity, Vec2),
+        candidates: &[(Entity, Vec2)],
+    ) -> Option<Entity> {
+        let strategy = &self.navigation.strategy;
+        let mut groups = self
+            .parents
+            .iter_ancestors(from.0)
+            .filter_map(|x| self.nav_edges.get(x).ok().map(|edge| (x, *edge)));
+        loop {
+            let (group, edge) = match groups.next() {
+                Some((group, edge)) => (Some(group), edge),
+                None => (None, self.navigation.edge),
+            };
+            let members: Vec<(Entity, Vec2)> = candidates
                 .iter()
-                .map(|(id, _, transform)| (id, transform.translation().xy()))
-        }) {
-            // info!("focus to {min_id}");
-            self.move_focus_to(min_id);
-        } else {
-            // warn!("no focus found");
+                .filter(|(id, _)| {
+                    group.is_none_or(|group| self.parents.iter_ancestors(*id).any(|x| x == group))
+                })
+                .copied()
+                .collect();
+            if let Some(id) = strategy.next(dir, from.1, &members) {
+                return Some(id);
+            }
+            match edge {
+                NavEdge::Wrap => return strategy.wrap(dir, from.1, &members),
+                NavEdge::Escape if group.is_some() => continue,
+                NavEdge::Stop | NavEdge::Escape => return None,
+            }
         }
     }
 
     /// Move focus to an entity.
     pub fn move_focus_to(&mut self, id: Entity) {
-        if let Some(old_focus) = self.focus.0.take() {
-            if let Ok((_, mut focusable, _)) = self.query.get_mut(old_focus) {
-                // Touch the old one so it knows it's no longer the focus.
-                focusable.touch()
-            }
-        }
-        self.focus.0 = Some(id);
+        self.set_focus_in(self.context_of(id), Some(id));
+    }
+
+    /// Move focus away from an entity, or the global focus, to the next one
+    /// in tab order.
+    pub fn move_focus_from(&mut self, id_maybe: impl Into<Option<Entity>>) {
+        let from = id_maybe.into().or(self.focus.0);
+        let context = from.and_then(|from| self.context_of(from));
+        let next = self.tab_from(context, from, true);
+        self.set_focus_in(context, next);
+    }
+
+    /// Move the global focus to the next entity in tab order.
+    pub fn move_focus_next(&mut self) {
+        self.tab_in(None, true);
+    }
+
+    /// Move the global focus to the previous entity in tab order.
+    pub fn move_focus_prev(&mut self) {
+        self.tab_in(None, false);
+    }
 
-        if let Ok((_, mut focusable, _)) = self.query.get_mut(id) {
-            // Touch the old one so it knows it's no longer the focus.
-            focusable.touch()
+    /// Move the focus of a [FocusContext], or the global focus for `None`, to
+    /// the next or previous entity in tab order.
+    pub fn tab_in(&mut self, context: Option<Entity>, forward: bool) {
+        if let Some(id) = self.tab_from(context, self.focused_in(context), forward) {
+            self.move_focus_to(id);
         }
     }
 
-    /// Move focus away from an entity.
-    pub fn move_focus_from(&mut self, id_maybe: impl Into<Option<Entity>>) {
-        if let Some(focus_id) = id_maybe.into().or(self.focus.0) {
-            // We're moving from a definite id.
-            let mut seen_id = false;
-            let mut result = None;
-            for (id, focusable, _) in &self.query {
-                if seen_id {
-                    result = Some(id);
-                    break;
-                }
-                if focus_id == id {
-                    seen_id = true;
-                } else if !focusable.block && result.is_none() {
-                    result = Some(id);
-                }
-            }
-            if let Some(id) = result {
-                let (_, mut focusable, _) = self.query.get_mut(id).unwrap();
-                focusable.touch();
-            }
-            self.focus.0 = result;
-        } else {
-            // We're moving to any available id.
-            self.focus.0 = self
-                .query
-                .iter_mut()
-                .find(|(_, focusable, _)| !focusable.block)
-                .map(|(id, mut focusable, _)| {
-                    focusable.touch();
-                    id
-                });
+    /// Return the unblocked entity of `context` after `from` in tab order,
+    /// wrapping around, or the first one if there is no `from`.
+    fn tab_from(
+        &self,
+        context: Option<Entity>,
+        from: Option<Entity>,
+        forward: bool,
+    ) -> Option<Entity> {
+        let tab_index = |id: Entity| self.tab_index.get(id).ok().copied();
+        let mut order: Vec<Entity> = self
+            .query
+            .iter()
+            .filter(|(id, focusable, _)| {
+                (Some(*id) == from
+                    || !focusable.block && tab_index(*id).is_none_or(|index| index.0 >= 0))
+                    && self.in_scope(*id)
+            })
+            .map(|(id, _, _)| id)
+            .chain(self.others.iter().filter_map(|(id, index)| {
+                (Some(id) == from || index.0 >= 0 && self.in_scope(id)).then_some(id)
+            }))
+            .filter(|id| self.context_of(*id) == context)
+            .collect();
+        order.sort_by_key(|id| {
+            let index = tab_index(*id);
+            (index.is_none(), index, *id)
+        });
+        if !forward {
+            order.reverse();
+        }
+        match from.and_then(|from| order.iter().position(|id| *id == from)) {
+            Some(i) => order[i + 1..].iter().chain(&order[..i]).next().copied(),
+            None => order.first().copied(),
         }
     }
 
@@ -193,6 +539,16 @@ impl FocusParam<'_, '_> {
         self.move_focus_from(id);
     }
 
+    /// Is focus on `id` lost, i.e., it is blocked, out of scope, or neither a
+    /// [Focusable] nor tabbable?
+    fn is_lost(&self, id: Entity) -> bool {
+        if self.others.contains(id) {
+            !self.in_scope(id)
+        } else {
+            self.is_blocked(id) || !self.in_scope(id)
+        }
+    }
+
     /// Is entity blocked?
     pub fn is_blocked(&self, id: Entity) -> bool {
         self.query
@@ -202,8 +558,13 @@ impl FocusParam<'_, '_> {
     }
 
     /// Block focus on current or given entity.
+    ///
+    /// [Editable] entities are not blocked.
     pub fn block(&mut self, id_maybe: impl Into<Option<Entity>>) {
         if let Some(id) = id_maybe.into().or(self.focus.0) {
+            if self.editable.contains(id) {
+                return;
+            }
             self.query
                 .get_mut(id)
                 .map(|(_, mut focus, _)| focus.block = true)
@@ -227,182 +588,272 @@ impl FocusParam<'_, '_> {
     }
 }
 
-fn focus_keys(input: Res<ButtonInput<KeyCode>>, mut focus: FocusParam) {
-    if !focus.keyboard_nav()
-        || !input.any_just_pressed([
-            KeyCode::ArrowUp,
-            KeyCode::ArrowDown,
-            KeyCode::ArrowLeft,
-            KeyCode::ArrowRight,
-        ])
-    {
+/// Each [FocusContext] with its keys, after the global one
+fn context_keys<'a>(
+    keys: &'a ButtonInput<KeyCode>,
+    contexts: &'a Query<(Entity, &FocusContext)>,
+) -> Vec<(Option<Entity>, &'a ButtonInput<KeyCode>)> {
+    std::iter::once((None, keys))
+        .chain(contexts.iter().map(|(id, c)| (Some(id), c.keys())))
+        .collect()
+}
+
+fn focus_keys(
+    keys: Res<ButtonInput<KeyCode>>,
+    contexts: Query<(Entity, &FocusContext)>,
+    text: Query<(), With<StringCursor>>,
+    mut focus: FocusParam,
+) {
+    if !focus.keyboard_nav() {
         return;
     }
-
-    if input.just_pressed(KeyCode::ArrowUp) {
-        focus.move_focus(CompassQuadrant::North);
-    } else if input.just_pressed(KeyCode::ArrowDown) {
-        focus.move_focus(CompassQuadrant::South);
-    } else if input.just_pressed(KeyCode::ArrowLeft) {
-        focus.move_focus(CompassQuadrant::West);
-    } else if input.just_pressed(KeyCode::ArrowRight) {
-        focus.move_focus(CompassQuadrant::East);
+    for (context, input) in context_keys(&keys, &contexts) {
+        // Arrow keys move the cursor of a focused text prompt instead.
+        if focus
+            .focused_in(context)
+            .is_some_and(|id| text.contains(id))
+        {
+            continue;
+        }
+        if input.just_pressed(KeyCode::ArrowUp) {
+            focus.move_focus_in(context, CompassQuadrant::North);
+        } else if input.just_pressed(KeyCode::ArrowDown) {
+            focus.move_focus_in(context, CompassQuadrant::South);
+        } else if input.just_pressed(KeyCode::ArrowLeft) {
+            focus.move_focus_in(context, CompassQuadrant::West);
+        } else if input.just_pressed(KeyCode::ArrowRight) {
+            focus.move_focus_in(context, CompassQuadrant::East);
+        }
     }
 }
 
-#[allow(dead_code)]
-fn focus_on_tab(input: Res<ButtonInput<KeyCode>>, mut focus: FocusParam) {
-    if input.just_pressed(KeyCode::Tab) {
-        focus.move_focus_from(None);
+fn focus_on_tab(
+    keys: Res<ButtonInput<KeyCode>>,
+    contexts: Query<(Entity, &FocusContext)>,
+    mut focus: FocusParam,
+) {
+    for (context, input) in context_keys(&keys, &contexts) {
+        if input.just_pressed(KeyCode::Tab) {
+            let back = input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+            focus.tab_in(context, !back);
+        }
     }
 }
 
-/// Reset focus if None or focus is blocked.
+/// Reset focus of each context if None, blocked, or out of scope.
 fn reset_focus(mut focus: FocusParam) {
-    match focus.focus.0 {
-        None => focus.move_focus_from(None),
-        Some(id) => {
-            if focus.is_blocked(id) {
-                focus.move_focus_from(None)
-            }
+    let contexts: Vec<Entity> = focus.contexts.iter().collect();
+    // Forget closed contexts.
+    focus
+        .context_focus
+        .0
+        .retain(|context, _| contexts.contains(context));
+    for context in std::iter::once(None).chain(contexts.into_iter().map(Some)) {
+        if focus
+            .focused_in(context)
+            .is_none_or(|id| focus.is_lost(id) || focus.context_of(id) != context)
+        {
+            let next = focus.tab_from(context, None, true);
+            focus.set_focus_in(context, next);
         }
     }
 }
 
-fn focus_next_rev<T>(
-    dir: Dir2,
-    curr: (T, Vec2),
-    elements: impl Iterator<Item = (T, Vec2)>,
-) -> Option<(T, f32)>
-where
-    T: PartialEq + Copy + Debug,
-{
-    let (curr_id, curr_pos) = curr;
-    elements
-        .filter_map(|(id, pos)| {
-            if id == curr_id {
-                None
-            } else {
-                let delta = pos - curr_pos;
-                let dirdist = delta.dot(*dir);
-                (dirdist > 0.0).then_some((id, dirdist))
-            }
-        })
-        .max_by(|a, b| a.1.total_cmp(&b.1))
-}
-
-fn focus_next_wrap<T, I>(dir: Dir2, curr: (T, Vec2), elements: impl Fn() -> I) -> Option<(T, f32)>
-where
-    T: PartialEq + Copy + Debug,
-    I: Iterator<Item = (T, Vec2)>,
-{
-    focus_next(dir, curr, elements()).or_else(|| focus_next_rev(-dir, curr, elements()))
-}
-
-fn focus_next<T>(
-    dir: Dir2,
-    curr: (T, Vec2),
-    elements: impl Iterator<Item = (T, Vec2)>,
-) -> Option<(T, f32)>
-where
-    T: PartialEq + Copy + Debug,
-{
-    let (curr_id, curr_pos) = curr;
-    elements
-        .filter_map(|(id, pos)| {
-            // dbg!(id, pos);
-            if id == curr_id {
-                None
-            } else {
-                let delta = pos - curr_pos;
-                let dirdist = delta.dot(*dir);
-                (dirdist > 0.0).then_some((id, dirdist))
-            }
-        })
-        .min_by(|a, b| a.1.total_cmp(&b.1))
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+
+    /// Make a world with the resources focus needs, but no systems.
+    fn world() -> World {
+        let mut world = World::new();
+        world.init_resource::<private::Focus>();
+        world.init_resource::<private::FocusScopes>();
+        world.init_resource::<private::ContextFocus>();
+        world.init_resource::<Navigation>();
+        world.insert_resource(KeyboardNav(true));
+        #[cfg(feature = "input_focus")]
+        {
+            world.init_resource::<private::Announced>();
+            world.init_resource::<DirectionalNavigationMap>();
+        }
+        world
+    }
 
-    #[test]
-    fn next_right() {
-        let elements = [(0, Vec2::ZERO), (1, Vec2::X)];
-        assert_eq!(
-            focus_next(
-                to_dir(CompassQuadrant::East),
-                elements[0],
-                elements.into_iter()
-            ),
-            Some((1, 1.0))
-        );
+    /// Run focus systems that the plugin would between our own.
+    fn settle(_world: &mut World) {
+        #[cfg(feature = "input_focus")]
+        _world.run_system_once(announce_focus).unwrap();
     }
 
     #[test]
-    fn two_right() {
-        let elements = [(0, Vec2::ZERO), (1, Vec2::X), (2, 2.0 * Vec2::X)];
-        assert_eq!(
-            focus_next(
-                to_dir(CompassQuadrant::East),
-                elements[0],
-                elements.into_iter()
-            ),
-            Some((1, 1.0))
-        );
+    fn tab_order() {
+        let mut world = world();
+        let mut spawn = |index: Option<i32>, block: bool| {
+            let mut entity = world.spawn((Focusable { block }, GlobalTransform::default()));
+            if let Some(index) = index {
+                entity.insert(TabIndex(index));
+            }
+            entity.id()
+        };
+        let c = spawn(None, false);
+        let b = spawn(Some(2), false);
+        let a = spawn(Some(1), false);
+        let _blocked = spawn(Some(0), true);
+        let _skipped = spawn(Some(-1), false);
+        let mut tab = |forward: bool| {
+            world
+                .run_system_once(move |mut focus: FocusParam| {
+                    if forward {
+                        focus.move_focus_next();
+                    } else {
+                        focus.move_focus_prev();
+                    }
+                    focus.focus.0
+                })
+                .unwrap()
+        };
+        assert_eq!(tab(true), Some(a));
+        assert_eq!(tab(true), Some(b));
+        assert_eq!(tab(true), Some(c));
+        assert_eq!(tab(true), Some(a));
+        assert_eq!(tab(false), Some(c));
     }
 
     #[test]
-    fn none_right() {
-        let elements = [(0, Vec2::ZERO), (1, Vec2::NEG_X)];
+    fn focus_events() {
+        let mut world = world();
+        let focusable = (Focusable::default(), GlobalTransform::default());
+        let a = world.spawn(focusable.clone()).id();
+        let b = world.spawn(focusable).id();
+        #[derive(Resource, Default)]
+        struct Log(Vec<(&'static str, Entity)>);
+        world.init_resource::<Log>();
+        world.add_observer(|trigger: Trigger<FocusGained>, mut log: ResMut<Log>| {
+            log.0.push(("gained", trigger.target()));
+        });
+        world.add_observer(|trigger: Trigger<FocusLost>, mut log: ResMut<Log>| {
+            log.0.push(("lost", trigger.target()));
+        });
+        for _ in 0..2 {
+            world
+                .run_system_once(|mut focus: FocusParam| focus.move_focus_next())
+                .unwrap();
+            settle(&mut world);
+        }
+        // Focusing the focused entity again changes nothing.
+        world
+            .run_system_once(move |mut focus: Focus| focus.focus_on(b))
+            .unwrap();
+        settle(&mut world);
         assert_eq!(
-            focus_next(
-                to_dir(CompassQuadrant::East),
-                elements[0],
-                elements.into_iter()
-            ),
-            None
+            world.resource::<Log>().0,
+            [("gained", a), ("lost", a), ("gained", b)]
         );
     }
 
     #[test]
-    fn none_right_wrap() {
-        let elements = [(0, Vec2::ZERO), (1, Vec2::NEG_X)];
-        assert_eq!(
-            focus_next_wrap(to_dir(CompassQuadrant::East), elements[0], || elements
-                .into_iter())
-            .map(|x| x.0),
-            Some(1)
-        );
+    fn focus_scope() {
+        let mut world = world();
+        let focusable = (Focusable::default(), GlobalTransform::default());
+        let outside = world.spawn(focusable.clone()).id();
+        world.spawn(focusable.clone());
+        world.resource_mut::<private::Focus>().0 = Some(outside);
+        let scope = world.spawn(FocusScope).id();
+        let a = world.spawn((focusable.clone(), ChildOf(scope))).id();
+        let b = world.spawn((focusable.clone(), ChildOf(scope))).id();
+        assert_eq!(world.resource::<private::Focus>().0, None);
+
+        world.run_system_once(reset_focus).unwrap();
+        assert_eq!(world.resource::<private::Focus>().0, Some(a));
+        for expected in [b, a] {
+            world
+                .run_system_once(|mut focus: FocusParam| focus.move_focus_next())
+                .unwrap();
+            assert_eq!(world.resource::<private::Focus>().0, Some(expected));
+        }
+
+        world.entity_mut(scope).despawn();
+        assert_eq!(world.resource::<private::Focus>().0, Some(outside));
     }
 
     #[test]
-    fn two_left_wrap() {
-        let elements = [(0, Vec2::ZERO), (1, Vec2::NEG_X), (2, 2.0 * Vec2::NEG_X)];
-        assert_eq!(
-            focus_next_wrap(to_dir(CompassQuadrant::East), elements[0], || elements
-                .into_iter())
-            .map(|x| x.0),
-            Some(2)
-        );
+    fn close_outer_scope() {
+        let mut world = world();
+        let focusable = (Focusable::default(), GlobalTransform::default());
+        let outside = world.spawn(focusable.clone()).id();
+        world.resource_mut::<private::Focus>().0 = Some(outside);
+        let outer = world.spawn(FocusScope).id();
+        let a = world.spawn((focusable.clone(), ChildOf(outer))).id();
+        world.run_system_once(reset_focus).unwrap();
+        assert_eq!(world.resource::<private::Focus>().0, Some(a));
+        let inner = world.spawn(FocusScope).id();
+        world.spawn((focusable.clone(), ChildOf(inner)));
+        world.run_system_once(reset_focus).unwrap();
+
+        // Closing the outer scope first closes the inner one too.
+        world.entity_mut(outer).remove::<FocusScope>();
+        assert_eq!(world.resource::<private::Focus>().0, Some(outside));
+        assert!(world.resource::<private::FocusScopes>().0.is_empty());
+        world.entity_mut(inner).despawn();
+        assert_eq!(world.resource::<private::Focus>().0, Some(outside));
     }
 
     #[test]
-    fn checkbox_group() {
-        let elements = [
-            (4, Vec2::new(258.0, 12.0)),
-            (5, Vec2::new(384.0, 12.0)),
-            (6, Vec2::new(510.0, 12.0)),
-            (8, Vec2::new(288.0, 60.0)),
-            (9, Vec2::new(288.0, 84.0)),
-            (10, Vec2::new(288.0, 108.0)),
-        ];
+    fn nav_edges() {
+        let mut world = world();
+        let group = world.spawn(NavEdge::Stop).id();
+        let at = |x: f32| (Focusable::default(), GlobalTransform::from_xyz(x, 0.0, 0.0));
+        let a = world.spawn((at(0.0), ChildOf(group))).id();
+        let b = world.spawn((at(10.0), ChildOf(group))).id();
+        let outside = world.spawn(at(20.0)).id();
+        world.resource_mut::<private::Focus>().0 = Some(a);
+        let east = |world: &mut World| {
+            world
+                .run_system_once(|mut focus: FocusParam| focus.move_focus(CompassQuadrant::East))
+                .unwrap();
+            world.resource::<private::Focus>().0.unwrap()
+        };
+        assert_eq!(east(&mut world), b);
+        assert_eq!(east(&mut world), b);
 
-        assert_eq!(
-            focus_next_wrap(to_dir(CompassQuadrant::East), elements[0], || elements
-                .into_iter())
-            .map(|x| x.0),
-            Some(8)
-        );
+        *world.get_mut::<NavEdge>(group).unwrap() = NavEdge::Wrap;
+        assert_eq!(east(&mut world), a);
+
+        *world.get_mut::<NavEdge>(group).unwrap() = NavEdge::Escape;
+        world.resource_mut::<private::Focus>().0 = Some(b);
+        assert_eq!(east(&mut world), outside);
+    }
+
+    #[cfg(feature = "input_focus")]
+    #[test]
+    fn other_ui() {
+        let mut world = world();
+        let a = world
+            .spawn((
+                Focusable::default(),
+                GlobalTransform::default(),
+                TabIndex(0),
+            ))
+            .id();
+        // A widget from other UI
+        let button = world.spawn(TabIndex(1)).id();
+        world
+            .resource_mut::<DirectionalNavigationMap>()
+            .add_symmetrical_edge(a, button, CompassOctant::East);
+
+        world.run_system_once(reset_focus).unwrap();
+        assert_eq!(world.resource::<private::Focus>().0, Some(a));
+        world
+            .run_system_once(|mut focus: FocusParam| focus.move_focus(CompassQuadrant::East))
+            .unwrap();
+        assert_eq!(world.resource::<private::Focus>().0, Some(button));
+        // Focus on other UI is kept.
+        world.run_system_once(reset_focus).unwrap();
+        assert_eq!(world.resource::<private::Focus>().0, Some(button));
+        world
+            .run_system_once(|mut focus: FocusParam| focus.move_focus_next())
+            .unwrap();
+        assert_eq!(world.resource::<private::Focus>().0, Some(a));
     }
 }
@@ -9,6 +9,7 @@ pub mod focus;
 #[cfg(feature = "async")]
 mod r#async;
 pub mod construct;
+mod expr;
 mod num_like;
 pub mod prompt;
 pub mod string_cursor;
@@ -19,6 +20,8 @@ use futures::channel::oneshot;
 pub use r#async::*;
 mod dest;
 pub mod sync;
+#[cfg(test)]
+mod test;
 pub use dest::Dest;
 
 /// Splat import, e.g., `use bevy_asky::prelude::*`
@@ -28,11 +31,11 @@ pub mod prelude {
     pub use super::{
         construct::*,
         focus::*,
-        num_like::NumLike,
+        num_like::{NumLike, NumberFormat},
         prompt::*,
         sync::{AskyCommands, AskyEntityCommands},
         view::{widget::Widgets, *},
-        AskyPlugin, AskySet, Dest, Error, Submit, Submitter,
+        AskyPlugin, AskySet, CurrentValue, Dest, Error, Submit, Submitter, ValueChanged,
     };
 }
 
@@ -88,6 +91,11 @@ impl Plugin for AskyPlugin {
 pub enum Submit<T> {
     /// Submit has not been handled yet.
     Unhandled(Result<T, Error>),
+    /// Submit changes an earlier answer and has not been handled yet.
+    ///
+    /// Only prompts that are submitted again, e.g., [prompt::Editable]
+    /// ones, fire these.
+    Edited(Result<T, Error>),
     /// Submit has been handled.
     Handled,
 }
@@ -98,15 +106,35 @@ impl<T> Submit<T> {
         Self::Unhandled(r)
     }
 
+    /// Create a submission event that changes an earlier answer.
+    pub fn edited(r: Result<T, Error>) -> Self {
+        Self::Edited(r)
+    }
+
+    /// Does this change an earlier answer?
+    ///
+    /// Check before taking the result.
+    pub fn is_edit(&self) -> bool {
+        matches!(self, Submit::Edited(_))
+    }
+
     /// Unwrap the result assuming it hasn't been taken already.
     pub fn take_result(&mut self) -> Result<T, Error> {
         match std::mem::replace(self, Submit::Handled) {
-            Submit::Unhandled(res) => res,
+            Submit::Unhandled(res) | Submit::Edited(res) => res,
             Submit::Handled => Err(Error::SubmitHandled),
         }
     }
 }
 
+/// Prompts trigger a ValueChanged when their value is edited
+///
+/// Unlike [Submit] it may fire many times before the prompt submits, e.g., to
+/// drive a live preview. It carries the prompt's own value, which is not
+/// mapped by a `MapSubmit`.
+#[derive(Event, Debug, Clone)]
+pub struct ValueChanged<T>(pub T);
+
 // /// Should we have a policy on submission?
 // #[derive(Debug, Component, Default, Clone)]
 // pub enum Submit {
@@ -127,6 +155,14 @@ pub unsafe trait Submitter {
     type Out;
 }
 
+/// Reads a prompt's current value without submitting it
+pub trait CurrentValue: Submitter {
+    /// Return the value prompt `id` would submit now.
+    ///
+    /// Validators are not checked.
+    fn current_value(world: &World, id: Entity) -> Result<Self::Out, Error>;
+}
+
 /// A part of a group
 pub trait Part {
     /// The type of the group
@@ -148,6 +184,9 @@ pub enum Error {
     /// Validation failed
     #[error("validation fail")]
     ValidationFail,
+    /// Prompt despawned before it submitted
+    #[error("despawned")]
+    Despawned,
     /// Submit handled already failure
     #[error("submit handled")]
     SubmitHandled,
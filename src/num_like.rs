@@ -1,12 +1,20 @@
 //! Number like trait
 use crate::string_cursor::StringCursor;
-use std::{fmt::Display, str::FromStr};
+use bevy::prelude::*;
+use std::{
+    fmt::Display,
+    num::{NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize},
+    str::FromStr,
+};
 
 /// A utility trait to allow only numbers in [`Number`] prompt.
 /// Also allows to custom handle they based on the type.
 ///
+/// Implement it for your own numeric types, e.g., fixed-point numbers or
+/// newtype IDs, to use them with [`Number`].
+///
 /// [`Number`]: crate::Number
-pub trait NumLike: Default + Display + FromStr + Send + Copy {
+pub trait NumLike: Display + FromStr + Send + Sync + Copy + PartialOrd {
     /// Check if it is a floating point number.
     fn is_float() -> bool {
         false
@@ -25,49 +33,381 @@ pub trait NumLike: Default + Display + FromStr + Send + Copy {
             _ => ch.is_ascii_digit(),
         }
     }
+
+    /// Is it neither infinite nor NaN?
+    fn is_finite(self) -> bool {
+        true
+    }
+
+    /// Step to use when none is given, or `None` if stepping is unsupported.
+    fn default_step() -> Option<Self> {
+        None
+    }
+
+    /// Add `step`, or `None` on overflow.
+    fn step_up(self, _step: Self) -> Option<Self> {
+        None
+    }
+
+    /// Subtract `step`, or `None` on overflow.
+    fn step_down(self, _step: Self) -> Option<Self> {
+        None
+    }
+
+    /// Parse digits in `radix`, or `None` if invalid or unsupported.
+    fn parse_radix(_digits: &str, _radix: u32) -> Option<Self> {
+        None
+    }
+
+    /// Format as digits in `radix`, or `None` if unsupported.
+    fn format_radix(self, _radix: u32) -> Option<String> {
+        None
+    }
+}
+
+/// How a [`Number`] prompt reads and writes numbers
+///
+/// The default accepts what [FromStr] does, less scientific notation.
+///
+/// ```
+/// # use bevy_asky::prelude::*;
+/// // 1.234,5
+/// let european = NumberFormat::default().decimal(',').grouping('.');
+/// // 0xFF, 0o17, 0b1010
+/// let debug = NumberFormat::default().radix_prefixes().scientific();
+/// ```
+///
+/// [`Number`]: crate::Number
+#[derive(Debug, Clone, PartialEq, Eq, Reflect)]
+pub struct NumberFormat {
+    /// Decimal separator
+    pub decimal: char,
+    /// Grouping separator, ignored when parsing
+    pub grouping: Option<char>,
+    /// Accept `0x`, `0o`, and `0b` prefixes for integers.
+    pub radix_prefixes: bool,
+    /// Accept scientific notation like `1e-3` for floats.
+    pub scientific: bool,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        NumberFormat {
+            decimal: '.',
+            grouping: None,
+            radix_prefixes: false,
+            scientific: false,
+        }
+    }
 }
 
-impl NumLike for u8 {}
-impl NumLike for u16 {}
-impl NumLike for u32 {}
-impl NumLike for u64 {}
-impl NumLike for u128 {}
-impl NumLike for usize {}
+impl NumberFormat {
+    /// Set decimal separator.
+    pub fn decimal(mut self, separator: char) -> Self {
+        self.decimal = separator;
+        self
+    }
+
+    /// Set grouping separator.
+    pub fn grouping(mut self, separator: char) -> Self {
+        self.grouping = Some(separator);
+        self
+    }
+
+    /// Accept `0x`, `0o`, and `0b` prefixes.
+    pub fn radix_prefixes(mut self) -> Self {
+        self.radix_prefixes = true;
+        self
+    }
+
+    /// Accept scientific notation.
+    pub fn scientific(mut self) -> Self {
+        self.scientific = true;
+        self
+    }
+
+    /// Return the sign, prefix, radix, and digits if `s` has a radix prefix.
+    fn split_radix(s: &str) -> Option<(&str, &str, u32, &str)> {
+        let (sign, rest) = match s.chars().next() {
+            Some('-' | '+') => s.split_at(1),
+            _ => ("", s),
+        };
+        let mut chars = rest.chars();
+        if chars.next() != Some('0') {
+            return None;
+        }
+        let radix = match chars.next()?.to_ascii_lowercase() {
+            'x' => 16,
+            'o' => 8,
+            'b' => 2,
+            _ => return None,
+        };
+        let (prefix, digits) = rest.split_at(2);
+        Some((sign, prefix, radix, digits))
+    }
+
+    /// May `ch` be inserted at the cursor of `input`?
+    pub fn is_valid<T: NumLike>(&self, ch: char, input: &StringCursor) -> bool {
+        let before = &input.value[..input.index];
+        // Does the type support radixes?
+        if self.radix_prefixes && T::parse_radix("0", 2).is_some() {
+            if let Some((sign, prefix, radix, _)) = Self::split_radix(&input.value) {
+                // Only digits after the prefix.
+                return input.index >= sign.len() + prefix.len() && ch.is_digit(radix);
+            }
+            if matches!(ch.to_ascii_lowercase(), 'x' | 'o' | 'b')
+                && matches!(before, "0" | "-0" | "+0")
+                && input.index == input.value.len()
+            {
+                return true;
+            }
+        }
+        if Some(ch) == self.grouping {
+            return input.index > 0;
+        }
+        let exponent = self.scientific && T::is_float();
+        if exponent && matches!(ch, 'e' | 'E') {
+            return input.index > 0 && !input.value.contains(['e', 'E']);
+        }
+        if exponent && matches!(ch, '-' | '+') && before.ends_with(['e', 'E']) {
+            return true;
+        }
+        if ch == self.decimal {
+            return T::is_float()
+                && !input.value.contains(self.decimal)
+                && !before.contains(['e', 'E']);
+        }
+        if ch == '.' {
+            return false;
+        }
+        T::is_valid(ch, input)
+    }
+
+    /// Parse a number.
+    pub fn parse<T: NumLike>(&self, s: &str) -> Option<T> {
+        let s: String = s.chars().filter(|ch| Some(*ch) != self.grouping).collect();
+        if self.radix_prefixes {
+            if let Some((sign, _, radix, digits)) = Self::split_radix(&s) {
+                return T::parse_radix(&format!("{sign}{digits}"), radix);
+            }
+        }
+        if !self.scientific && s.contains(['e', 'E']) {
+            return None;
+        }
+        s.replace(self.decimal, ".").parse().ok()
+    }
+
+    /// Format a number.
+    pub fn format<T: NumLike>(&self, value: T) -> String {
+        let s = value.to_string();
+        let (sign, s) = match s.strip_prefix('-') {
+            Some(s) => ("-", s),
+            None => ("", s.as_str()),
+        };
+        let (int, frac) = match s.find('.') {
+            Some(i) => (&s[..i], Some(&s[i + 1..])),
+            None => (s, None),
+        };
+        let mut out = String::from(sign);
+        for (i, ch) in int.chars().enumerate() {
+            if let Some(grouping) = self.grouping {
+                if i > 0 && (int.len() - i) % 3 == 0 && int.is_ascii() {
+                    out.push(grouping);
+                }
+            }
+            out.push(ch);
+        }
+        if let Some(frac) = frac {
+            out.push(self.decimal);
+            out.push_str(frac);
+        }
+        out
+    }
+
+    /// Format a number in the same radix as `like`.
+    pub fn format_like<T: NumLike>(&self, value: T, like: &str) -> String {
+        if let Some((_, prefix, radix, _)) = Self::split_radix(like).filter(|_| self.radix_prefixes)
+        {
+            if let Some(digits) = value.format_radix(radix) {
+                return match digits.strip_prefix('-') {
+                    Some(digits) => format!("-{prefix}{digits}"),
+                    None => format!("{prefix}{digits}"),
+                };
+            }
+        }
+        self.format(value)
+    }
+}
+
+fn radix_digits(mut n: u128, radix: u32) -> String {
+    let radix = u128::from(radix);
+    let mut digits = Vec::new();
+    loop {
+        digits.push(char::from_digit((n % radix) as u32, radix as u32).unwrap_or('?'));
+        n /= radix;
+        if n == 0 {
+            break;
+        }
+    }
+    digits.iter().rev().collect()
+}
+
+macro_rules! int_radix {
+    (unsigned) => {
+        fn parse_radix(digits: &str, radix: u32) -> Option<Self> {
+            Self::from_str_radix(digits, radix).ok()
+        }
+
+        fn format_radix(self, radix: u32) -> Option<String> {
+            Some(radix_digits(u128::try_from(self).ok()?, radix))
+        }
+    };
+    (signed) => {
+        fn parse_radix(digits: &str, radix: u32) -> Option<Self> {
+            Self::from_str_radix(digits, radix).ok()
+        }
+
+        fn format_radix(self, radix: u32) -> Option<String> {
+            let digits = radix_digits(u128::try_from(self.unsigned_abs()).ok()?, radix);
+            Some(if self < 0 {
+                format!("-{digits}")
+            } else {
+                digits
+            })
+        }
+    };
+}
+
+macro_rules! int_step {
+    () => {
+        fn default_step() -> Option<Self> {
+            Some(1)
+        }
+
+        fn step_up(self, step: Self) -> Option<Self> {
+            self.checked_add(step)
+        }
+
+        fn step_down(self, step: Self) -> Option<Self> {
+            self.checked_sub(step)
+        }
+    };
+}
+
+macro_rules! non_zero_step {
+    () => {
+        fn default_step() -> Option<Self> {
+            Self::new(1)
+        }
+
+        fn step_up(self, step: Self) -> Option<Self> {
+            self.checked_add(step.get())
+        }
+
+        fn step_down(self, step: Self) -> Option<Self> {
+            self.get().checked_sub(step.get()).and_then(Self::new)
+        }
+    };
+}
+
+macro_rules! float_step {
+    () => {
+        fn default_step() -> Option<Self> {
+            Some(1.0)
+        }
+
+        fn step_up(self, step: Self) -> Option<Self> {
+            Some(self + step).filter(|x| x.is_finite())
+        }
+
+        fn step_down(self, step: Self) -> Option<Self> {
+            Some(self - step).filter(|x| x.is_finite())
+        }
+    };
+}
+
+impl NumLike for u8 {
+    int_step!();
+    int_radix!(unsigned);
+}
+
+impl NumLike for u16 {
+    int_step!();
+    int_radix!(unsigned);
+}
+
+impl NumLike for u32 {
+    int_step!();
+    int_radix!(unsigned);
+}
+
+impl NumLike for u64 {
+    int_step!();
+    int_radix!(unsigned);
+}
+
+impl NumLike for u128 {
+    int_step!();
+    int_radix!(unsigned);
+}
+
+impl NumLike for usize {
+    int_step!();
+    int_radix!(unsigned);
+}
 
 impl NumLike for i8 {
     fn is_signed() -> bool {
         true
     }
+
+    int_step!();
+    int_radix!(signed);
 }
 
 impl NumLike for i16 {
     fn is_signed() -> bool {
         true
     }
+
+    int_step!();
+    int_radix!(signed);
 }
 
 impl NumLike for i32 {
     fn is_signed() -> bool {
         true
     }
+
+    int_step!();
+    int_radix!(signed);
 }
 
 impl NumLike for i64 {
     fn is_signed() -> bool {
         true
     }
+
+    int_step!();
+    int_radix!(signed);
 }
 
 impl NumLike for i128 {
     fn is_signed() -> bool {
         true
     }
+
+    int_step!();
+    int_radix!(signed);
 }
 
 impl NumLike for isize {
     fn is_signed() -> bool {
         true
     }
+
+    int_step!();
+    int_radix!(signed);
 }
 
 impl NumLike for f32 {
@@ -78,6 +418,12 @@ impl NumLike for f32 {
     fn is_float() -> bool {
         true
     }
+
+    fn is_finite(self) -> bool {
+        f32::is_finite(self)
+    }
+
+    float_step!();
 }
 
 impl NumLike for f64 {
@@ -88,4 +434,82 @@ impl NumLike for f64 {
     fn is_float() -> bool {
         true
     }
+
+    fn is_finite(self) -> bool {
+        f64::is_finite(self)
+    }
+
+    float_step!();
+}
+
+impl NumLike for NonZeroU8 {
+    non_zero_step!();
+}
+
+impl NumLike for NonZeroU16 {
+    non_zero_step!();
+}
+
+impl NumLike for NonZeroU32 {
+    non_zero_step!();
+}
+
+impl NumLike for NonZeroU64 {
+    non_zero_step!();
+}
+
+impl NumLike for NonZeroU128 {
+    non_zero_step!();
+}
+
+impl NumLike for NonZeroUsize {
+    non_zero_step!();
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn cursor(s: &str) -> StringCursor {
+        let mut cursor = StringCursor::default();
+        cursor.set_value(s);
+        cursor
+    }
+
+    #[test]
+    fn european() {
+        let format = NumberFormat::default().decimal(',').grouping('.');
+        assert_eq!(format.parse::<f64>("1.234,5"), Some(1234.5));
+        assert_eq!(format.format(1234.5f64), "1.234,5");
+        assert_eq!(format.format(-123456i32), "-123.456");
+        assert!(format.is_valid::<f64>(',', &cursor("1.234")));
+        assert!(!format.is_valid::<f64>(',', &cursor("1,2")));
+        assert!(!format.is_valid::<i32>(',', &cursor("1")));
+    }
+
+    #[test]
+    fn radix() {
+        let format = NumberFormat::default().radix_prefixes();
+        assert_eq!(format.parse::<u8>("0xFF"), Some(255));
+        assert_eq!(format.parse::<i32>("-0b1010"), Some(-10));
+        assert_eq!(format.parse::<u8>("0o17"), Some(15));
+        assert_eq!(format.parse::<u8>("0x100"), None);
+        assert_eq!(format.format_like(-11i32, "-0b1010"), "-0b1011");
+        assert_eq!(format.format_like(16u8, "0XF"), "0X10");
+        assert!(format.is_valid::<u8>('x', &cursor("0")));
+        assert!(format.is_valid::<u8>('f', &cursor("0x")));
+        assert!(!format.is_valid::<u8>('2', &cursor("0b1")));
+        assert!(!format.is_valid::<f32>('x', &cursor("0")));
+    }
+
+    #[test]
+    fn scientific() {
+        let format = NumberFormat::default().scientific();
+        assert_eq!(format.parse::<f64>("1e-3"), Some(0.001));
+        assert!(format.is_valid::<f64>('e', &cursor("1")));
+        assert!(format.is_valid::<f64>('-', &cursor("1e")));
+        assert!(!format.is_valid::<f64>('.', &cursor("1e")));
+        assert!(!format.is_valid::<i32>('e', &cursor("1")));
+        assert_eq!(NumberFormat::default().parse::<f64>("1e-3"), None);
+    }
 }
@@ -24,6 +24,8 @@ pub(crate) fn plugin(app: &mut App) {
         PreUpdate,
         (checkbox_controller, checkbox_group_controller).in_set(AskySet::Controller),
     );
+    #[cfg(any(feature = "color", feature = "ascii"))]
+    app.add_observer(checkbox_pointer);
 }
 
 impl Part for Checkbox {
@@ -50,25 +52,36 @@ impl Construct for Checkbox {
 
 fn checkbox_controller(
     focus: Focus,
-    mut query: Query<(Entity, &mut Checkbox)>,
-    input: Res<ButtonInput<KeyCode>>,
+    mut query: Query<(Entity, &mut Checkbox, Option<&ChildOf>)>,
+    validating: Query<(), With<Validating>>,
+    input: ContextInput,
     // mut requests: EventWriter<NavRequest>,
 ) {
     use KeyCode::*;
 
-    if input.any_just_pressed([Space, KeyY, KeyN]) {
-        for (id, mut checkbox) in query.iter_mut() {
+    for (id, mut checkbox, parent) in query.iter_mut() {
+        let input = input.keys(id);
+        if input.any_just_pressed([Space, KeyY, KeyN]) {
             if !focus.is_focused(id) {
                 continue;
             }
+            // Hold still while the group is being validated.
+            if parent.is_some_and(|p| validating.contains(p.parent())) {
+                continue;
+            }
+            let mut checked = checkbox.checked;
             if input.just_pressed(Space) {
-                checkbox.checked = !checkbox.checked;
+                checked = !checked;
             }
             if input.any_just_pressed([KeyY]) {
-                checkbox.checked = true;
+                checked = true;
             }
             if input.any_just_pressed([KeyN]) {
-                checkbox.checked = false;
+                checked = false;
+            }
+            // Only write real changes so the group sees them.
+            if checked != checkbox.checked {
+                checkbox.checked = checked;
             }
 
             // if input.just_pressed(Enter) {
@@ -85,6 +98,25 @@ fn checkbox_controller(
     }
 }
 
+/// Toggle the checkbox pressed with the pointer.
+#[cfg(any(feature = "color", feature = "ascii"))]
+fn checkbox_pointer(
+    trigger: Trigger<PointerSelect>,
+    mut query: Query<(&mut Checkbox, Option<&ChildOf>)>,
+    validating: Query<(), With<Validating>>,
+) {
+    if *trigger.event() != PointerSelect::Check {
+        return;
+    }
+    let Ok((mut checkbox, parent)) = query.get_mut(trigger.target()) else {
+        return;
+    };
+    if parent.is_some_and(|p| validating.contains(p.parent())) {
+        return;
+    }
+    checkbox.checked = !checkbox.checked;
+}
+
 // impl Component for Checkbox {
 //     const STORAGE_TYPE: StorageType = StorageType::Table;
 
@@ -105,12 +137,24 @@ fn checkbox_controller(
 ///
 /// Given to parent of checkbox group that handles submission.
 #[derive(Component, Reflect, Default)]
+#[require(AnswerGuard<Vec<bool>>)]
 pub struct CheckboxGroup;
 
 unsafe impl Submitter for CheckboxGroup {
     type Out = Vec<bool>;
 }
 
+impl CurrentValue for CheckboxGroup {
+    fn current_value(world: &World, id: Entity) -> Result<Vec<bool>, Error> {
+        let children = world.get::<Children>(id).ok_or(Error::InvalidInput)?;
+        Ok(children
+            .iter()
+            .filter_map(|child| world.get::<Checkbox>(child))
+            .map(|checkbox| checkbox.checked)
+            .collect())
+    }
+}
+
 impl Construct for CheckboxGroup {
     type Props = Cow<'static, str>;
 
@@ -132,27 +176,51 @@ impl Construct for CheckboxGroup {
 }
 
 fn checkbox_group_controller(
-    mut query: Query<(Entity, &Children), With<CheckboxGroup>>,
-    checkboxes: Query<(Entity, &Checkbox)>,
-    input: Res<ButtonInput<KeyCode>>,
+    mut query: Query<
+        (
+            Entity,
+            &Children,
+            Option<&Validator<Vec<bool>>>,
+            Option<&MapSubmit<Vec<bool>>>,
+        ),
+        (With<CheckboxGroup>, Without<Validating>),
+    >,
+    checkboxes: Query<(Entity, Ref<Checkbox>)>,
+    input: ContextInput,
     mut commands: Commands,
     focus: FocusParam,
 ) {
-    if !input.any_just_pressed([KeyCode::Escape, KeyCode::Enter]) {
-        return;
+    let checked = |children: &Children| -> Vec<bool> {
+        checkboxes
+            .iter_many(children)
+            .map(|(_, checkbox)| checkbox.checked)
+            .collect()
+    };
+    for (id, children, validator, _) in &query {
+        if checkboxes
+            .iter_many(children)
+            .any(|(_, checkbox)| checkbox.is_changed() && !checkbox.is_added())
+        {
+            let value = checked(children);
+            validate_live(id, validator, &value, &mut commands);
+            value_changed::<CheckboxGroup>(id, value, &mut commands);
+        }
     }
-    for (id, children) in query.iter_mut() {
+    for (id, children, validator, map) in query.iter_mut() {
+        let input = input.keys(id);
+        if !input.any_just_pressed([KeyCode::Escape, KeyCode::Enter]) {
+            continue;
+        }
         if children.iter().any(|id| focus.is_focused(id)) {
             if input.just_pressed(KeyCode::Enter) {
-                let result: Vec<bool> = checkboxes
-                    .iter_many(children)
-                    .map(|(_, checkbox)| checkbox.checked)
-                    .collect();
-                commands.trigger_targets(Submit::new(Ok(result)), id);
+                let result = checked(children);
+                if validate(id, validator, &result, false, &mut commands) {
+                    submit::<CheckboxGroup>(id, map, Ok(result), &mut commands);
+                }
             }
 
             if input.just_pressed(KeyCode::Escape) {
-                commands.trigger_targets(Submit::<Vec<bool>>::new(Err(Error::Cancel)), id);
+                submit::<CheckboxGroup>(id, map, Err(Error::Cancel), &mut commands);
             }
         }
     }
@@ -4,6 +4,7 @@ use std::borrow::Cow;
 
 /// Confirm query
 #[derive(Debug, Component, Reflect)]
+#[require(AnswerGuard<bool>)]
 pub struct Confirm {
     /// Yes or no
     pub yes: bool,
@@ -30,8 +31,19 @@ unsafe impl Submitter for Confirm {
     type Out = bool;
 }
 
+impl CurrentValue for Confirm {
+    fn current_value(world: &World, id: Entity) -> Result<bool, Error> {
+        world
+            .get::<Self>(id)
+            .map(|confirm| confirm.yes)
+            .ok_or(Error::InvalidInput)
+    }
+}
+
 pub(crate) fn plugin(app: &mut App) {
     app.add_systems(Update, confirm_controller.in_set(AskySet::Controller));
+    #[cfg(any(feature = "color", feature = "ascii"))]
+    app.add_observer(confirm_pointer);
 }
 
 impl Construct for Confirm {
@@ -53,15 +65,24 @@ impl Construct for Confirm {
 }
 
 fn confirm_controller(
-    mut query: Query<(Entity, &mut Confirm)>,
-    input: Res<ButtonInput<KeyCode>>,
+    mut query: Query<
+        (
+            Entity,
+            &mut Confirm,
+            Option<&Validator<bool>>,
+            Option<&MapSubmit<bool>>,
+        ),
+        Without<Validating>,
+    >,
+    input: ContextInput,
     mut commands: Commands,
     focus: FocusParam,
 ) {
-    for (id, mut confirm) in query.iter_mut() {
+    for (id, mut confirm, validator, map) in query.iter_mut() {
         if !focus.is_focused(id) {
             continue;
         }
+        let input = input.keys(id);
         if input.any_just_pressed([
             KeyCode::KeyY,
             KeyCode::ArrowRight,
@@ -72,23 +93,50 @@ fn confirm_controller(
             KeyCode::Enter,
             KeyCode::Escape,
         ]) {
+            let before = confirm.yes;
             if input.any_just_pressed([KeyCode::KeyY, KeyCode::KeyL, KeyCode::ArrowRight]) {
                 confirm.yes = true;
             }
             if input.any_just_pressed([KeyCode::KeyN, KeyCode::KeyH, KeyCode::ArrowLeft]) {
                 confirm.yes = false;
             }
-            if input.just_pressed(KeyCode::Enter) {
+            if confirm.yes != before {
+                validate_live(id, validator, &confirm.yes, &mut commands);
+                value_changed::<Confirm>(id, confirm.yes, &mut commands);
+            }
+            if input.just_pressed(KeyCode::Enter)
+                && validate(id, validator, &confirm.yes, false, &mut commands)
+            {
                 // Make this not focusable again.
                 // I had tried using triggers in bevy_ui_navigation to fix my issues.
                 // commands.trigger(NavRequest::Move(NavDirection::South));
-                commands.trigger_targets(Submit::<bool>::new(Ok(confirm.yes)), id);
+                submit::<Confirm>(id, map, Ok(confirm.yes), &mut commands);
                 // focus.block_and_move(id);
             }
             if input.just_pressed(KeyCode::Escape) {
-                commands.trigger_targets(Submit::<bool>::new(Err(Error::Cancel)), id);
+                submit::<Confirm>(id, map, Err(Error::Cancel), &mut commands);
                 // commands.entity(id).try_insert(Feedback::error("canceled"));
             }
         }
     }
 }
+
+/// Select the option pressed with the pointer.
+#[cfg(any(feature = "color", feature = "ascii"))]
+fn confirm_pointer(
+    trigger: Trigger<PointerSelect>,
+    mut query: Query<(&mut Confirm, Option<&Validator<bool>>), Without<Validating>>,
+    mut commands: Commands,
+) {
+    let id = trigger.target();
+    let (PointerSelect::Option(index), Ok((mut confirm, validator))) =
+        (*trigger.event(), query.get_mut(id))
+    else {
+        return;
+    };
+    if confirm.yes != (index == 1) {
+        confirm.yes = index == 1;
+        validate_live(id, validator, &confirm.yes, &mut commands);
+        value_changed::<Confirm>(id, confirm.yes, &mut commands);
+    }
+}
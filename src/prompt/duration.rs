@@ -0,0 +1,302 @@
+use crate::{construct::*, prelude::*, string_cursor::*};
+use bevy::{
+    input::keyboard::{Key, KeyboardInput},
+    prelude::*,
+};
+use std::{borrow::Cow, fmt::Write, time::Duration};
+
+pub(crate) fn plugin(app: &mut App) {
+    app.add_systems(Update, duration_controller.in_set(AskySet::Controller));
+}
+
+/// Prompt for a span of time like `1h30m`, `90s`, or `250ms`
+///
+/// Units are `d`, `h`, `m`, `s`, `ms`, `us` or `µs`, and `ns`. Amounts may
+/// have a fractional part like `1.5h`.
+///
+/// # Key Events
+///
+/// | Key         | Action                       |
+/// | ----------- | ---------------------------- |
+/// | `Enter`     | Submit current value         |
+/// | `Backspace` | Delete previous character    |
+/// | `Delete`    | Delete current character     |
+/// | `Left`      | Move cursor left             |
+/// | `Right`     | Move cursor right            |
+///
+#[derive(Debug, Clone, Default, Component, Reflect)]
+#[require(AnswerGuard<Duration>)]
+pub struct DurationPrompt {
+    /// Minimum duration
+    pub min: Option<Duration>,
+    /// Maximum duration
+    pub max: Option<Duration>,
+}
+
+unsafe impl Submitter for DurationPrompt {
+    type Out = Duration;
+}
+
+impl CurrentValue for DurationPrompt {
+    fn current_value(world: &World, id: Entity) -> Result<Duration, Error> {
+        let (Some(prompt), Some(text_state)) =
+            (world.get::<Self>(id), world.get::<StringCursor>(id))
+        else {
+            return Err(Error::InvalidInput);
+        };
+        parse_duration(&text_state.value)
+            .and_then(|duration| prompt.bound(duration))
+            .map_err(|_| Error::InvalidInput)
+    }
+}
+
+impl Construct for DurationPrompt {
+    type Props = Cow<'static, str>;
+
+    fn construct(
+        context: &mut ConstructContext,
+        props: Self::Props,
+    ) -> Result<Self, ConstructError> {
+        // Our requirements.
+        let input_state = StringCursor::default();
+        let mut commands = context.world.commands();
+        commands
+            .entity(context.id)
+            .insert(Prompt(props))
+            .insert(input_state)
+            .insert(Focusable::default());
+        context.world.flush();
+        Ok(DurationPrompt::default())
+    }
+}
+
+impl DurationPrompt {
+    /// Set minimum duration.
+    pub fn min(mut self, value: Duration) -> Self {
+        self.min = Some(value);
+        self
+    }
+
+    /// Set maximum duration.
+    pub fn max(mut self, value: Duration) -> Self {
+        self.max = Some(value);
+        self
+    }
+
+    /// Check `value` is within bounds.
+    pub fn bound(&self, value: Duration) -> Result<Duration, Cow<'static, str>> {
+        if let Some(min) = self.min.filter(|min| value < *min) {
+            return Err(format!("must be at least {}", format_duration(min)).into());
+        }
+        if let Some(max) = self.max.filter(|max| value > *max) {
+            return Err(format!("must be at most {}", format_duration(max)).into());
+        }
+        Ok(value)
+    }
+}
+
+const UNITS: [(&str, u128); 8] = [
+    ("d", 86_400_000_000_000),
+    ("h", 3_600_000_000_000),
+    ("m", 60_000_000_000),
+    ("s", 1_000_000_000),
+    ("ms", 1_000_000),
+    ("us", 1_000),
+    ("µs", 1_000),
+    ("ns", 1),
+];
+
+/// May `ch` appear in a duration?
+fn is_valid(ch: char) -> bool {
+    ch.is_ascii_digit() || matches!(ch, '.' | ' ' | 'd' | 'h' | 'm' | 's' | 'u' | 'µ' | 'n')
+}
+
+/// Parse a duration like `1h30m`, `90s`, or `250ms`.
+pub fn parse_duration(s: &str) -> Result<Duration, Cow<'static, str>> {
+    let mut nanos: u128 = 0;
+    let mut rest = s.trim_start();
+    if rest.is_empty() {
+        return Err("empty duration".into());
+    }
+    let overflow = || Cow::from("duration too long");
+    while !rest.is_empty() {
+        let amount_len = rest
+            .find(|ch: char| !ch.is_ascii_digit() && ch != '.')
+            .unwrap_or(rest.len());
+        let (amount, tail) = rest.split_at(amount_len);
+        let unit_len = tail
+            .find(|ch: char| !ch.is_alphabetic())
+            .unwrap_or(tail.len());
+        let (unit, tail) = tail.split_at(unit_len);
+        let (int, frac) = amount.split_once('.').unwrap_or((amount, ""));
+        if int.is_empty() && frac.is_empty() {
+            return Err(format!("expected a number before {unit:?}").into());
+        }
+        if unit.is_empty() {
+            return Err(format!("missing unit after {amount}").into());
+        }
+        let Some((_, scale)) = UNITS.iter().find(|(name, _)| *name == unit) else {
+            return Err(format!("unknown unit {unit:?}").into());
+        };
+        let int: u128 = if int.is_empty() {
+            0
+        } else {
+            int.parse().map_err(|_| overflow())?
+        };
+        nanos = int
+            .checked_mul(*scale)
+            .and_then(|x| nanos.checked_add(x))
+            .ok_or_else(overflow)?;
+        // Add the fraction one digit at a time, truncating below a nanosecond.
+        let mut place = *scale;
+        for digit in frac.chars() {
+            let digit = digit.to_digit(10).ok_or("invalid number")?;
+            place /= 10;
+            nanos = u128::from(digit)
+                .checked_mul(place)
+                .and_then(|x| nanos.checked_add(x))
+                .ok_or_else(overflow)?;
+        }
+        rest = tail.trim_start();
+    }
+    let secs = u64::try_from(nanos / 1_000_000_000).map_err(|_| overflow())?;
+    Ok(Duration::new(secs, (nanos % 1_000_000_000) as u32))
+}
+
+/// Format a duration like `1h 30m` or `250ms`.
+pub fn format_duration(duration: Duration) -> String {
+    let mut nanos = duration.as_nanos();
+    if nanos == 0 {
+        return "0s".into();
+    }
+    let mut out = String::new();
+    for (unit, scale) in UNITS.iter().filter(|(unit, _)| *unit != "us") {
+        let amount = nanos / scale;
+        nanos %= scale;
+        if amount > 0 {
+            if !out.is_empty() {
+                out.push(' ');
+            }
+            let _ = write!(out, "{amount}{unit}");
+        }
+    }
+    out
+}
+
+fn duration_controller(
+    mut query: Query<
+        (
+            Entity,
+            &mut StringCursor,
+            &DurationPrompt,
+            Option<&Validator<Duration>>,
+            Option<&MapSubmit<Duration>>,
+        ),
+        Without<Validating>,
+    >,
+    mut events: EventReader<KeyboardInput>,
+    input: ContextInput,
+    mut commands: Commands,
+    mut focus: FocusParam,
+) {
+    let events: Vec<KeyboardInput> = events.read().cloned().collect();
+    for (id, mut text_state, prompt, validator, map) in query.iter_mut() {
+        if !focus.is_focused(id) {
+            continue;
+        }
+        let before = text_state.value.clone();
+        for key in input.logical_keys(id, &events) {
+            match &key {
+                Key::Character(s) => {
+                    for c in s.chars() {
+                        if is_valid(c) {
+                            text_state.insert(c);
+                        }
+                    }
+                }
+                Key::Space => text_state.insert(' '),
+                Key::Backspace => text_state.backspace(),
+                Key::Delete => text_state.delete(),
+                Key::ArrowLeft => text_state.move_cursor(CursorDirection::Left),
+                Key::ArrowRight => text_state.move_cursor(CursorDirection::Right),
+                Key::Enter => {
+                    match parse_duration(&text_state.value).and_then(|d| prompt.bound(d)) {
+                        Ok(duration) => {
+                            if validate(id, validator, &duration, true, &mut commands)
+                                && submit::<DurationPrompt>(id, map, Ok(duration), &mut commands)
+                            {
+                                focus.block(id);
+                                focus.move_focus_from(id);
+                            }
+                        }
+                        Err(message) => {
+                            commands.entity(id).try_insert(Feedback::warn(message));
+                        }
+                    }
+                }
+                Key::Escape => {
+                    submit::<DurationPrompt>(id, map, Err(Error::Cancel), &mut commands);
+                    commands.entity(id).try_insert(Feedback::error("canceled"));
+                    focus.block(id);
+                    focus.move_focus_from(id);
+                }
+                _ => {}
+            }
+        }
+        if text_state.value != before {
+            if let Ok(duration) = parse_duration(&text_state.value) {
+                validate_live(id, validator, &duration, &mut commands);
+                if let Ok(duration) = prompt.bound(duration) {
+                    value_changed::<DurationPrompt>(id, duration, &mut commands);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!(parse_duration("1h30m"), Ok(Duration::from_secs(5400)));
+        assert_eq!(parse_duration("90s"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("250ms"), Ok(Duration::from_millis(250)));
+        assert_eq!(
+            parse_duration(" 1m 1.5s "),
+            Ok(Duration::from_millis(61_500))
+        );
+        assert_eq!(parse_duration("2µs3ns"), Ok(Duration::from_nanos(2003)));
+        assert_eq!(parse_duration(".5d"), Ok(Duration::from_secs(43_200)));
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("90").is_err());
+        assert!(parse_duration("1x").is_err());
+        assert_eq!(
+            parse_duration("3938453320844195178974243.9d"),
+            Err("duration too long".into())
+        );
+        assert!(parse_duration("h").is_err());
+        assert!(parse_duration("1.2.3s").is_err());
+        assert!(parse_duration("999999999999999999999d").is_err());
+    }
+
+    #[test]
+    fn format() {
+        assert_eq!(format_duration(Duration::from_secs(5400)), "1h 30m");
+        assert_eq!(format_duration(Duration::from_secs(90)), "1m 30s");
+        assert_eq!(format_duration(Duration::from_millis(250)), "250ms");
+        assert_eq!(format_duration(Duration::from_nanos(2003)), "2µs 3ns");
+        assert_eq!(format_duration(Duration::ZERO), "0s");
+    }
+
+    #[test]
+    fn bound() {
+        let prompt = DurationPrompt::default()
+            .min(Duration::from_secs(1))
+            .max(Duration::from_secs(60));
+        assert!(prompt.bound(Duration::from_secs(30)).is_ok());
+        assert!(prompt.bound(Duration::from_millis(500)).is_err());
+        assert!(prompt.bound(Duration::from_secs(61)).is_err());
+    }
+}
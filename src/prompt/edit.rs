@@ -0,0 +1,33 @@
+use crate::construct::*;
+use bevy::prelude::*;
+
+/// Keeps a prompt open for edits after it is answered
+///
+/// The prompt is never blocked from focus, so it may be focused and submitted
+/// again. While it is unfocused, color views show its answer in place of its
+/// input, and ascii views mark it with `+`. Later submissions are
+/// [Submit::Edited](crate::Submit::Edited), so observers can tell the first
+/// answer from changes to it.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_asky::prelude::*;
+/// fn setup(mut commands: Commands) {
+///     commands
+///         .prompt::<Add0<Confirm, color::View>>("Vsync? ", Dest::Root)
+///         .insert(Editable);
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Component, Reflect)]
+pub struct Editable;
+
+impl Construct for Editable {
+    type Props = ();
+
+    fn construct(
+        _context: &mut ConstructContext,
+        _props: Self::Props,
+    ) -> Result<Self, ConstructError> {
+        Ok(Editable)
+    }
+}
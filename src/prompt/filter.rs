@@ -0,0 +1,163 @@
+use crate::{construct::*, string_cursor::*};
+use bevy::prelude::*;
+use std::{borrow::Cow, sync::Arc};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Predicate for [InputFilter]
+pub type CharPredicate = dyn Fn(char, &StringCursor) -> bool + Send + Sync;
+
+/// Classes of characters an [InputFilter] may allow
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum CharClass {
+    /// Alphabetic characters
+    Alphabetic,
+    /// Numeric characters
+    Numeric,
+    /// Alphabetic or numeric characters
+    Alphanumeric,
+    /// ASCII alphabetic or numeric characters
+    AsciiAlphanumeric,
+    /// Whitespace characters
+    Whitespace,
+    /// ASCII punctuation characters
+    Punctuation,
+}
+
+impl CharClass {
+    /// Does the character belong to this class?
+    pub fn contains(&self, ch: char) -> bool {
+        use CharClass::*;
+        match self {
+            Alphabetic => ch.is_alphabetic(),
+            Numeric => ch.is_numeric(),
+            Alphanumeric => ch.is_alphanumeric(),
+            AsciiAlphanumeric => ch.is_ascii_alphanumeric(),
+            Whitespace => ch.is_whitespace(),
+            Punctuation => ch.is_ascii_punctuation(),
+        }
+    }
+}
+
+/// Filters the characters a text prompt accepts
+///
+/// Add to a [TextField] or [Password] entity. A keystroke is accepted only if
+/// it passes every configured check.
+///
+/// ```
+/// # use bevy_asky::prelude::*;
+/// let filter = InputFilter::default()
+///     .max_len(16)
+///     .allow(CharClass::Alphanumeric)
+///     .warn();
+/// ```
+#[derive(Component, Clone, Default)]
+pub struct InputFilter {
+    /// Maximum length in graphemes
+    pub max_len: Option<usize>,
+    /// Allowed character classes; all characters are allowed if empty.
+    pub allowed: Vec<CharClass>,
+    /// Custom predicate
+    pub predicate: Option<Arc<CharPredicate>>,
+    /// Show a [Feedback] warning when a keystroke is rejected.
+    ///
+    /// [Feedback]: crate::prompt::Feedback
+    pub warn: bool,
+}
+
+impl InputFilter {
+    /// Set the maximum length in graphemes.
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len);
+        self
+    }
+
+    /// Allow a class of characters.
+    pub fn allow(mut self, class: CharClass) -> Self {
+        self.allowed.push(class);
+        self
+    }
+
+    /// Set a custom predicate.
+    pub fn predicate(
+        mut self,
+        predicate: impl Fn(char, &StringCursor) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.predicate = Some(Arc::new(predicate));
+        self
+    }
+
+    /// Show a warning when a keystroke is rejected.
+    pub fn warn(mut self) -> Self {
+        self.warn = true;
+        self
+    }
+
+    /// Check whether `ch` may be inserted into `input`.
+    ///
+    /// Returns the reason for rejection otherwise.
+    pub fn check(&self, ch: char, input: &StringCursor) -> Result<(), Cow<'static, str>> {
+        if !self.allowed.is_empty() && !self.allowed.iter().any(|class| class.contains(ch)) {
+            return Err(format!("character {ch:?} not allowed").into());
+        }
+        if let Some(max_len) = self.max_len {
+            let mut value = input.value.clone();
+            value.insert(input.index, ch);
+            if value.graphemes(true).count() > max_len {
+                return Err(format!("at most {max_len} characters").into());
+            }
+        }
+        if let Some(predicate) = &self.predicate {
+            if !predicate(ch, input) {
+                return Err(format!("character {ch:?} not allowed").into());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Construct for InputFilter {
+    type Props = InputFilter;
+    fn construct(
+        _context: &mut ConstructContext,
+        props: Self::Props,
+    ) -> Result<Self, ConstructError> {
+        Ok(props)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn cursor(s: &str) -> StringCursor {
+        let mut cursor = StringCursor::default();
+        cursor.set_value(s);
+        cursor
+    }
+
+    #[test]
+    fn player_name() {
+        let filter = InputFilter::default()
+            .max_len(4)
+            .allow(CharClass::Alphanumeric);
+        assert!(filter.check('a', &cursor("abc")).is_ok());
+        assert!(filter.check('1', &cursor("abc")).is_ok());
+        assert!(filter.check(' ', &cursor("abc")).is_err());
+        assert!(filter.check('a', &cursor("abcd")).is_err());
+    }
+
+    #[test]
+    fn max_len_graphemes() {
+        let filter = InputFilter::default().max_len(2);
+        // A combining acute accent does not start a new grapheme.
+        assert!(filter.check('\u{301}', &cursor("ae")).is_ok());
+        assert!(filter.check('e', &cursor("ae\u{301}")).is_err());
+    }
+
+    #[test]
+    fn predicate() {
+        let filter = InputFilter::default().predicate(|ch, input| ch != '_' || input.index > 0);
+        assert!(filter.check('_', &cursor("")).is_err());
+        assert!(filter.check('_', &cursor("a")).is_ok());
+    }
+}
@@ -0,0 +1,174 @@
+use crate::{construct::*, prelude::*};
+use bevy::{ecs::bundle::BundleFromComponents, prelude::*};
+use std::{borrow::Cow, marker::PhantomData, sync::Arc};
+
+/// Function for [Mapper]
+pub type MapFn<In, Out> = dyn Fn(In) -> Result<Out, Cow<'static, str>> + Send + Sync;
+
+type SubmitFn<In> =
+    dyn Fn(Result<In, Error>, Entity, &mut Commands) -> Result<(), Cow<'static, str>> + Send + Sync;
+
+/// Maps an answer of type `In` to `Out`
+///
+/// [Map] keeps it on its prompt to map the [CurrentValue].
+#[derive(Component)]
+pub struct Mapper<In: 'static, Out: 'static>(Arc<MapFn<In, Out>>);
+
+impl<In, Out> Clone for Mapper<In, Out> {
+    fn clone(&self) -> Self {
+        Mapper(self.0.clone())
+    }
+}
+
+impl<In, Out> Mapper<In, Out> {
+    /// Make a mapper from a function.
+    pub fn new(map: impl Fn(In) -> Result<Out, Cow<'static, str>> + Send + Sync + 'static) -> Self {
+        Mapper(Arc::new(map))
+    }
+}
+
+/// Maps a prompt's answer before it is submitted
+///
+/// Add to a prompt entity whose output is `In`, and observers receive
+/// `Submit<Out>` instead of `Submit<In>`. If mapping fails the prompt shows an
+/// error [Feedback] and does not submit.
+///
+/// ```
+/// # use bevy_asky::prelude::*;
+/// // Observe `Submit<u64>` from a `TextField`.
+/// let map = MapSubmit::new(|id: String| {
+///     id.trim()
+///         .parse::<u64>()
+///         .map_err(|_| "expected an id".into())
+/// });
+/// ```
+///
+/// See [Map] to change the [Submitter::Out] of a prompt type.
+#[derive(Component)]
+pub struct MapSubmit<In: 'static>(Arc<SubmitFn<In>>);
+
+impl<In> Clone for MapSubmit<In> {
+    fn clone(&self) -> Self {
+        MapSubmit(self.0.clone())
+    }
+}
+
+impl<In: 'static> MapSubmit<In> {
+    /// Map answers with a function.
+    pub fn new<Out: Send + Sync + 'static>(
+        map: impl Fn(In) -> Result<Out, Cow<'static, str>> + Send + Sync + 'static,
+    ) -> Self {
+        Mapper::new(map).into()
+    }
+
+    /// Map `result` and trigger [Submit] with it.
+    pub(crate) fn submit(
+        &self,
+        result: Result<In, Error>,
+        id: Entity,
+        commands: &mut Commands,
+    ) -> Result<(), Cow<'static, str>> {
+        (self.0)(result, id, commands)
+    }
+}
+
+impl<In: 'static, Out: Send + Sync + 'static> From<Mapper<In, Out>> for MapSubmit<In> {
+    fn from(mapper: Mapper<In, Out>) -> Self {
+        MapSubmit(Arc::new(move |result, id, commands| {
+            let result = match result {
+                Ok(value) => Ok((mapper.0)(value)?),
+                Err(e) => Err(e),
+            };
+            trigger_submit(id, result, commands);
+            Ok(())
+        }))
+    }
+}
+
+impl<In: Send + Sync + 'static> Construct for MapSubmit<In> {
+    type Props = MapSubmit<In>;
+    fn construct(
+        _context: &mut ConstructContext,
+        props: Self::Props,
+    ) -> Result<Self, ConstructError> {
+        Ok(props)
+    }
+}
+
+/// Prompt `P` whose answer is mapped to `Out`
+///
+/// Its [Submitter::Out] is `Out`, so it may be used wherever the output type
+/// matters, e.g., with `AskyAsync::prompt()`.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_asky::prelude::*;
+/// fn setup(mut commands: Commands) {
+///     commands.prompt::<Map<Add0<TextField, color::View>, u64>>(
+///         (
+///             "Id? ".into(),
+///             Mapper::new(|id: String| id.trim().parse().map_err(|_| "expected an id".into())),
+///         ),
+///         Dest::Root,
+///     );
+/// }
+/// ```
+#[derive(Bundle)]
+pub struct Map<P, Out>
+where
+    P: Submitter + Bundle + BundleFromComponents,
+    P::Out: Send + Sync + 'static,
+    Out: Send + Sync + 'static,
+{
+    prompt: P,
+    map: MapSubmit<P::Out>,
+    mapper: Mapper<P::Out, Out>,
+    #[bundle(ignore)]
+    _out: PhantomData<Out>,
+}
+
+unsafe impl<P, Out> Submitter for Map<P, Out>
+where
+    P: Submitter + Bundle + BundleFromComponents,
+    P::Out: Send + Sync + 'static,
+    Out: Send + Sync + 'static,
+{
+    type Out = Out;
+}
+
+impl<P, Out> CurrentValue for Map<P, Out>
+where
+    P: CurrentValue + Bundle + BundleFromComponents,
+    P::Out: Send + Sync + 'static,
+    Out: Send + Sync + 'static,
+{
+    fn current_value(world: &World, id: Entity) -> Result<Out, Error> {
+        let value = P::current_value(world, id)?;
+        let mapper = world
+            .get::<Mapper<P::Out, Out>>(id)
+            .ok_or(Error::InvalidInput)?;
+        (mapper.0)(value).map_err(|_| Error::InvalidInput)
+    }
+}
+
+impl<P, Out> Construct for Map<P, Out>
+where
+    P: Construct + Submitter + Bundle + BundleFromComponents,
+    P::Out: Send + Sync + 'static,
+    Out: Send + Sync + 'static,
+{
+    type Props = (P::Props, Mapper<P::Out, Out>);
+
+    fn construct(
+        context: &mut ConstructContext,
+        props: Self::Props,
+    ) -> Result<Self, ConstructError> {
+        let prompt = P::construct(context, props.0)?;
+        Ok(Map {
+            prompt,
+            map: props.1.clone().into(),
+            mapper: props.1,
+            _out: PhantomData,
+        })
+    }
+}
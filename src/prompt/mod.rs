@@ -1,4 +1,4 @@
-//! Checkbox, Confirm, Number, Password, Radio, TextField, Toggle
+//! Checkbox, Confirm, DurationPrompt, Number, Password, Radio, TextField, Toggle
 use crate::construct::*;
 use bevy::prelude::*;
 use std::borrow::Cow;
@@ -6,18 +6,30 @@ use std::fmt;
 
 mod checkbox;
 mod confirm;
+mod duration;
+mod edit;
+mod filter;
+mod map;
 mod number;
 mod password;
 mod radio;
+mod submit;
 mod text;
 mod toggle;
+mod validate;
 pub use checkbox::*;
 pub use confirm::*;
+pub use duration::*;
+pub use edit::*;
+pub use filter::*;
+pub use map::*;
 pub use number::*;
 pub use password::*;
 pub use radio::*;
+pub use submit::*;
 pub use text::*;
 pub use toggle::*;
+pub use validate::*;
 
 /// Prompt new type
 #[derive(Component, Deref, DerefMut, Reflect)]
@@ -88,6 +100,14 @@ pub struct Feedback {
 }
 
 impl Feedback {
+    /// No feedback
+    pub fn none() -> Self {
+        Feedback {
+            kind: FeedbackKind::None,
+            message: "".into(),
+        }
+    }
+
     /// Clear feedback.
     pub fn clear(&mut self) {
         self.kind = FeedbackKind::None;
@@ -169,13 +189,328 @@ pub(crate) fn plugin(app: &mut App) {
         .register_type::<TextField>()
         .register_type::<Password>()
         .register_type::<Toggle>()
+        .register_type::<CharClass>()
+        .register_type::<DurationPrompt>()
+        .register_type::<Validating>()
+        .register_type::<Editable>()
         .add_plugins((
             confirm::plugin,
+            duration::plugin,
             text::plugin,
             number::plugin,
             password::plugin,
             toggle::plugin,
             checkbox::plugin,
             radio::plugin,
+            submit::plugin,
         ));
 }
+
+#[cfg(all(test, not(feature = "focus")))]
+mod test {
+    use super::*;
+    use crate::{
+        prelude::*,
+        test::{app, press},
+        Submitter,
+    };
+    use bevy::{ecs::system::RunSystemOnce, input::keyboard::Key};
+    use std::sync::{Arc, Mutex};
+
+    /// Spawn a focused prompt and return the app and the prompt.
+    ///
+    /// `spawn` returns the prompt and the entity to focus.
+    fn setup(spawn: impl FnOnce(&mut Commands) -> Entity) -> (App, Entity) {
+        let mut app = app();
+        let world = app.world_mut();
+        let id = spawn(&mut world.commands());
+        world.flush();
+        // Focus only considers laid out entities.
+        let focusables: Vec<Entity> = world
+            .query_filtered::<Entity, With<Focusable>>()
+            .iter(world)
+            .collect();
+        for focusable in focusables {
+            world
+                .entity_mut(focusable)
+                .insert(GlobalTransform::default());
+        }
+        let focus_id = world
+            .get::<Children>(id)
+            .and_then(|children| {
+                children
+                    .iter()
+                    .find(|x| world.get::<Focusable>(*x).is_some())
+            })
+            .unwrap_or(id);
+        world
+            .run_system_once(move |mut focus: Focus| focus.focus_on(focus_id))
+            .unwrap();
+        (app, id)
+    }
+
+    /// Collect the errors prompt `id` submits with output `T`.
+    fn errors<T: Send + Sync + 'static>(world: &mut World, id: Entity) -> Arc<Mutex<Vec<Error>>> {
+        let errors = Arc::new(Mutex::new(Vec::new()));
+        let sink = errors.clone();
+        world
+            .entity_mut(id)
+            .observe(move |mut trigger: Trigger<Submit<T>>| {
+                if let Err(e) = trigger.event_mut().take_result() {
+                    sink.lock().unwrap().push(e);
+                }
+            });
+        errors
+    }
+
+    fn press_escape(world: &mut World) {
+        press(world, KeyCode::Escape, Key::Escape);
+    }
+
+    /// Collect the values prompt `id` changes to.
+    fn changes<T: Clone + Send + Sync + 'static>(
+        world: &mut World,
+        id: Entity,
+    ) -> Arc<Mutex<Vec<T>>> {
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let sink = changes.clone();
+        world
+            .entity_mut(id)
+            .observe(move |trigger: Trigger<ValueChanged<T>>| {
+                sink.lock().unwrap().push(trigger.event().0.clone());
+            });
+        changes
+    }
+
+    /// Press `Escape` on a prompt `P` and return whether it submitted a
+    /// cancel of its own output type.
+    fn cancels<P: Submitter>(spawn: impl FnOnce(&mut Commands) -> Entity) -> bool
+    where
+        P::Out: Send + Sync + 'static,
+    {
+        let (mut app, id) = setup(spawn);
+        let errors = errors::<P::Out>(app.world_mut(), id);
+        press_escape(app.world_mut());
+        app.update();
+        let errors = errors.lock().unwrap();
+        matches!(errors[..], [Error::Cancel])
+    }
+
+    #[test]
+    fn cancel_text_field() {
+        assert!(cancels::<TextField>(|commands| commands
+            .construct::<TextField>("Name? ")
+            .id()));
+    }
+
+    #[test]
+    fn cancel_password() {
+        assert!(cancels::<Password>(|commands| commands
+            .construct::<Password>("Password? ")
+            .id()));
+    }
+
+    #[test]
+    fn cancel_number() {
+        assert!(cancels::<Number<i32>>(|commands| commands
+            .construct::<Number<i32>>("Age? ")
+            .id()));
+    }
+
+    #[test]
+    fn cancel_duration() {
+        assert!(cancels::<DurationPrompt>(|commands| commands
+            .construct::<DurationPrompt>("Cooldown? ")
+            .id()));
+    }
+
+    #[test]
+    fn cancel_confirm() {
+        assert!(cancels::<Confirm>(|commands| commands
+            .construct::<Confirm>("Sure? ")
+            .id()));
+    }
+
+    #[test]
+    fn cancel_toggle() {
+        assert!(cancels::<Toggle>(|commands| commands
+            .construct::<Toggle>(Toggle::new("Which? ", ["this", "that"]))
+            .id()));
+    }
+
+    #[test]
+    fn cancel_radio_group() {
+        assert!(cancels::<RadioGroup>(|commands| commands
+            .construct::<RadioGroup>("Which? ")
+            .construct_children::<Radio>(["this", "that"])
+            .id()));
+    }
+
+    #[test]
+    fn cancel_checkbox_group() {
+        assert!(cancels::<CheckboxGroup>(|commands| commands
+            .construct::<CheckboxGroup>("Which? ")
+            .construct_children::<Checkbox>(["this", "that"])
+            .id()));
+    }
+
+    #[test]
+    fn cancel_mapped() {
+        assert!(cancels::<Map<TextField, u64>>(|commands| commands
+            .construct::<Map<TextField, u64>>((
+                "Id? ".into(),
+                Mapper::new(|id: String| id.parse().map_err(|_| "expected an id".into())),
+            ))
+            .id()));
+    }
+
+    #[test]
+    fn validating_group_holds_still() {
+        let (mut app, id) = setup(|commands| {
+            commands
+                .construct::<CheckboxGroup>("Which? ")
+                .construct_children::<Checkbox>(["this", "that"])
+                .id()
+        });
+        let world = app.world();
+        let first = world
+            .get::<Children>(id)
+            .unwrap()
+            .iter()
+            .find(|x| world.get::<Checkbox>(*x).is_some())
+            .unwrap();
+        app.world_mut().entity_mut(id).insert(Validating);
+        press(app.world_mut(), KeyCode::Space, Key::Space);
+        app.update();
+        assert!(!app.world().get::<Checkbox>(first).unwrap().checked);
+
+        app.world_mut().entity_mut(id).remove::<Validating>();
+        press(app.world_mut(), KeyCode::Space, Key::Space);
+        app.update();
+        assert!(app.world().get::<Checkbox>(first).unwrap().checked);
+    }
+
+    #[test]
+    fn despawn_unanswered() {
+        let (mut app, id) = setup(|commands| commands.construct::<TextField>("Name? ").id());
+        let errors = errors::<String>(app.world_mut(), id);
+        app.world_mut().entity_mut(id).despawn();
+        app.update();
+        assert!(matches!(errors.lock().unwrap()[..], [Error::Despawned]));
+    }
+
+    #[test]
+    fn despawn_unanswered_mapped() {
+        let (mut app, id) = setup(|commands| {
+            commands
+                .construct::<Map<TextField, u64>>((
+                    "Id? ".into(),
+                    Mapper::new(|id: String| id.parse().map_err(|_| "expected an id".into())),
+                ))
+                .id()
+        });
+        let errors = errors::<u64>(app.world_mut(), id);
+        app.world_mut().entity_mut(id).despawn();
+        app.update();
+        assert!(matches!(errors.lock().unwrap()[..], [Error::Despawned]));
+    }
+
+    #[test]
+    fn despawn_answered() {
+        let (mut app, id) = setup(|commands| commands.construct::<TextField>("Name? ").id());
+        let errors = errors::<String>(app.world_mut(), id);
+        press_escape(app.world_mut());
+        app.update();
+        assert!(app.world().get::<Unanswered>(id).is_none());
+        app.world_mut().entity_mut(id).despawn();
+        app.update();
+        assert!(matches!(errors.lock().unwrap()[..], [Error::Cancel]));
+    }
+
+    #[test]
+    fn value_changed() {
+        let (mut app, id) = setup(|commands| commands.construct::<TextField>("Name? ").id());
+        let changes = changes::<String>(app.world_mut(), id);
+        press(app.world_mut(), KeyCode::KeyA, Key::Character("a".into()));
+        app.update();
+        // Moving the cursor doesn't change the value.
+        press(app.world_mut(), KeyCode::ArrowLeft, Key::ArrowLeft);
+        app.update();
+        assert_eq!(*changes.lock().unwrap(), ["a"]);
+        assert_eq!(TextField::current_value(app.world(), id).unwrap(), "a");
+    }
+
+    #[test]
+    fn current_value_mapped() {
+        let (mut app, id) = setup(|commands| {
+            commands
+                .construct::<Map<TextField, u64>>((
+                    "Id? ".into(),
+                    Mapper::new(|id: String| id.parse().map_err(|_| "expected an id".into())),
+                ))
+                .id()
+        });
+        let changes = changes::<String>(app.world_mut(), id);
+        assert!(Map::<TextField, u64>::current_value(app.world(), id).is_err());
+        press(app.world_mut(), KeyCode::Digit7, Key::Character("7".into()));
+        app.update();
+        assert_eq!(*changes.lock().unwrap(), ["7"]);
+        assert_eq!(
+            Map::<TextField, u64>::current_value(app.world(), id).unwrap(),
+            7
+        );
+    }
+
+    #[test]
+    fn resubmit_editable() {
+        let (mut app, id) = setup(|commands| {
+            commands
+                .construct::<Number<i32>>("Age? ")
+                .insert(Editable)
+                .id()
+        });
+        let submits = Arc::new(Mutex::new(Vec::new()));
+        let sink = submits.clone();
+        app.world_mut()
+            .entity_mut(id)
+            .observe(move |mut trigger: Trigger<Submit<i32>>| {
+                let edit = trigger.event().is_edit();
+                let value = trigger.event_mut().take_result().unwrap();
+                sink.lock().unwrap().push((edit, value));
+            });
+        for (key_code, key) in [
+            (KeyCode::Digit4, Key::Character("4".into())),
+            (KeyCode::Enter, Key::Enter),
+            (KeyCode::Digit2, Key::Character("2".into())),
+            (KeyCode::Enter, Key::Enter),
+        ] {
+            press(app.world_mut(), key_code, key);
+            app.update();
+        }
+        // It is never blocked, so focus returns to it.
+        assert_eq!(*submits.lock().unwrap(), [(false, 4), (true, 42)]);
+        assert!(app.world().get::<Unanswered>(id).is_none());
+    }
+
+    #[cfg(any(feature = "color", feature = "ascii"))]
+    #[test]
+    fn pointer_select() {
+        use crate::string_cursor::StringCursor;
+        let (mut app, id) = setup(|commands| commands.construct::<Confirm>("Ok? ").id());
+        let changes = changes::<bool>(app.world_mut(), id);
+        app.world_mut()
+            .trigger_targets(PointerSelect::Option(1), id);
+        app.world_mut().flush();
+        assert!(app.world().get::<Confirm>(id).unwrap().yes);
+        assert_eq!(*changes.lock().unwrap(), [true]);
+
+        let (mut app, id) = setup(|commands| commands.construct::<TextField>("Name? ").id());
+        app.world_mut()
+            .get_mut::<StringCursor>(id)
+            .unwrap()
+            .set_value("abc");
+        app.world_mut()
+            .trigger_targets(PointerSelect::MoveCursor(-2), id);
+        assert_eq!(app.world().get::<StringCursor>(id).unwrap().index, 1);
+    }
+}
@@ -1,32 +1,69 @@
-use crate::{construct::*, prelude::*, string_cursor::*};
+use crate::{construct::*, expr, prelude::*, string_cursor::*};
 use bevy::{
-    input::{
-        keyboard::{Key, KeyboardInput},
-        ButtonState,
-    },
+    input::keyboard::{Key, KeyboardInput},
     prelude::*,
 };
-use std::borrow::Cow;
+use std::{any::TypeId, borrow::Cow, collections::HashSet};
 
 pub(crate) fn plugin(app: &mut App) {
-    app.add_systems(
-        Update,
-        (
-            number_controller::<f32>,
-            number_controller::<f64>,
-            number_controller::<i8>,
-            number_controller::<i16>,
-            number_controller::<i32>,
-            number_controller::<i64>,
-            number_controller::<isize>,
-            number_controller::<u8>,
-            number_controller::<u16>,
-            number_controller::<u32>,
-            number_controller::<u64>,
-            number_controller::<usize>,
-        )
-            .in_set(AskySet::Controller),
-    );
+    app.init_resource::<NumberTypes>()
+        .register_number::<f32>()
+        .register_number::<f64>()
+        .register_number::<i8>()
+        .register_number::<i16>()
+        .register_number::<i32>()
+        .register_number::<i64>()
+        .register_number::<i128>()
+        .register_number::<isize>()
+        .register_number::<u8>()
+        .register_number::<u16>()
+        .register_number::<u32>()
+        .register_number::<u64>()
+        .register_number::<u128>()
+        .register_number::<usize>()
+        .add_systems(Update, number_controller.in_set(AskySet::Controller));
+}
+
+/// Registers [Number] types with an [App]
+///
+/// Constructing a [Number] registers its type as needed, so this is only
+/// necessary for [Number] components inserted some other way.
+pub trait NumberAppExt {
+    /// Handle `Number<T>` prompts.
+    fn register_number<T: NumLike + TypePath + Sync + 'static>(&mut self) -> &mut Self;
+}
+
+impl NumberAppExt for App {
+    fn register_number<T: NumLike + TypePath + Sync + 'static>(&mut self) -> &mut Self {
+        register_number::<T>(self.world_mut());
+        self
+    }
+}
+
+/// Types of [Number] with observers
+#[derive(Resource, Default)]
+struct NumberTypes(HashSet<TypeId>);
+
+fn register_number<T: NumLike + TypePath + Sync + 'static>(world: &mut World) {
+    if world
+        .get_resource_or_init::<NumberTypes>()
+        .0
+        .insert(TypeId::of::<T>())
+    {
+        world.add_observer(number_key::<T>);
+        world.add_observer(number_placeholder::<T>);
+    }
+}
+
+/// Marks a [Number] of any type
+#[derive(Component, Clone, Copy, Default)]
+struct NumberInput;
+
+/// Forwards a key press to a typed [Number].
+#[derive(Event, Debug, Clone)]
+struct NumberKey {
+    key: Key,
+    shift: bool,
 }
 
 // pub type InputValidator<'a> = dyn Fn(&str) -> Result<(), Cow<'a, str>> + 'a + Send + Sync;
@@ -35,25 +72,49 @@ pub(crate) fn plugin(app: &mut App) {
 ///
 /// # Key Events
 ///
-/// | Key         | Action                       |
-/// | ----------- | ---------------------------- |
-/// | `Enter`     | Submit current/initial value |
-/// | `Backspace` | Delete previous character    |
-/// | `Delete`    | Delete current character     |
-/// | `Left`      | Move cursor left             |
-/// | `Right`     | Move cursor right            |
+/// | Key            | Action                       |
+/// | -------------- | ---------------------------- |
+/// | `Enter`        | Submit current/default value |
+/// | `Backspace`    | Delete previous character    |
+/// | `Delete`       | Delete current character     |
+/// | `Left`         | Move cursor left             |
+/// | `Right`        | Move cursor right            |
+/// | `Up`           | Increment by step            |
+/// | `Down`         | Decrement by step            |
+/// | `Shift+Up`     | Increment by ten steps       |
+/// | `Shift+Down`   | Decrement by ten steps       |
 ///
+/// The default value is shown as the [Placeholder] unless one is given.
+/// Numbers are read and written according to its [NumberFormat]. In
+/// expression mode, arithmetic like `64*3+8` is evaluated on submit.
 #[derive(Debug, Clone, Component, Reflect)]
+#[require(AnswerGuard<T>, NumberInput)]
 pub struct Number<T: NumLike> {
+    /// How numbers are read and written
+    pub format: NumberFormat,
     /// Default value to submit when the input is empty
     pub default_value: Option<T>,
+    /// Minimum value
+    pub min: Option<T>,
+    /// Maximum value
+    pub max: Option<T>,
+    /// Increment for `Up` and `Down`; [NumLike::default_step] if `None`.
+    pub step: Option<T>,
+    /// Clamp out-of-bounds values on submit instead of warning.
+    pub clamp: bool,
+    /// Stepping past one bound wraps around to the other.
+    ///
+    /// Only applies if both `min` and `max` are given.
+    pub wrap: bool,
+    /// Evaluate `+`, `-`, `*`, `/`, `%`, and parentheses on submit.
+    pub expression: bool,
 }
 
 unsafe impl<T: NumLike> Submitter for Number<T> {
     type Out = T;
 }
 
-impl<T: NumLike> Construct for Number<T> {
+impl<T: NumLike + TypePath + Sync + 'static> Construct for Number<T> {
     type Props = Cow<'static, str>;
 
     fn construct(
@@ -62,6 +123,7 @@ impl<T: NumLike> Construct for Number<T> {
     ) -> Result<Self, ConstructError> {
         // Our requirements.
         let input_state = StringCursor::default();
+        register_number::<T>(context.world);
         let mut commands = context.world.commands();
         commands
             .entity(context.id)
@@ -69,84 +131,379 @@ impl<T: NumLike> Construct for Number<T> {
             .insert(input_state)
             .insert(Focusable::default());
         context.world.flush();
-        Ok(Number {
-            default_value: None,
-        })
+        Ok(Number::new())
     }
 }
 
 impl<T: NumLike> Number<T> {
+    /// Make an unbounded number prompt.
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Number {
+            format: NumberFormat::default(),
+            default_value: None,
+            min: None,
+            max: None,
+            step: None,
+            clamp: false,
+            wrap: false,
+            expression: false,
+        }
+    }
+
+    /// Set how numbers are read and written.
+    pub fn format(mut self, format: NumberFormat) -> Self {
+        self.format = format;
+        self
+    }
+
     /// Set default value to submit when the input is empty.
     pub fn default(mut self, value: T) -> Self {
         self.default_value = Some(value);
         self
     }
+
+    /// Set minimum value.
+    pub fn min(mut self, value: T) -> Self {
+        self.min = Some(value);
+        self
+    }
+
+    /// Set maximum value.
+    pub fn max(mut self, value: T) -> Self {
+        self.max = Some(value);
+        self
+    }
+
+    /// Set increment for `Up` and `Down`.
+    pub fn step(mut self, value: T) -> Self {
+        self.step = Some(value);
+        self
+    }
+
+    /// Clamp out-of-bounds values on submit instead of warning.
+    pub fn clamp(mut self) -> Self {
+        self.clamp = true;
+        self
+    }
+
+    /// Wrap around when stepping past a bound.
+    pub fn wrap(mut self) -> Self {
+        self.wrap = true;
+        self
+    }
+
+    /// Evaluate arithmetic expressions on submit.
+    pub fn expression(mut self) -> Self {
+        self.expression = true;
+        self
+    }
+
+    /// Check `value` is within bounds, clamping it if configured.
+    pub fn bound(&self, value: T) -> Result<T, Cow<'static, str>> {
+        if let Some(min) = self.min.filter(|min| value < *min) {
+            return if self.clamp {
+                Ok(min)
+            } else {
+                Err(format!("must be at least {}", self.format.format(min)).into())
+            };
+        }
+        if let Some(max) = self.max.filter(|max| value > *max) {
+            return if self.clamp {
+                Ok(max)
+            } else {
+                Err(format!("must be at most {}", self.format.format(max)).into())
+            };
+        }
+        Ok(value)
+    }
+
+    /// Step `value` up or down `times` steps staying within bounds.
+    pub fn step_by(&self, mut value: T, up: bool, times: usize) -> T {
+        let Some(step) = self.step.or_else(T::default_step) else {
+            return value;
+        };
+        let in_bounds =
+            |x: &T| self.min.is_none_or(|min| *x >= min) && self.max.is_none_or(|max| *x <= max);
+        for _ in 0..times {
+            let next = if up {
+                value.step_up(step)
+            } else {
+                value.step_down(step)
+            };
+            match next.filter(in_bounds) {
+                Some(next) => value = next,
+                None => {
+                    let (bound, other) = if up {
+                        (self.max, self.min)
+                    } else {
+                        (self.min, self.max)
+                    };
+                    value = match (bound, other) {
+                        // At the bound already, so wrap around.
+                        (Some(bound), Some(other)) if self.wrap && value == bound => other,
+                        (Some(bound), _) => bound,
+                        (None, _) => value,
+                    };
+                    break;
+                }
+            }
+        }
+        value
+    }
+}
+
+impl<T: NumLike + TypePath> Number<T> {
+    /// Parse input, evaluating it in expression mode.
+    pub fn parse(&self, input: &str) -> Result<T, Cow<'static, str>> {
+        if self.expression {
+            expr::eval(input, &self.format).map_err(|e| e.to_string().into())
+        } else {
+            self.format
+                .parse(input)
+                .ok_or_else(|| format!("invalid number for {}", T::short_type_path()).into())
+        }
+    }
+
+    /// Parse input, or return the default value if it is empty.
+    pub fn value(&self, input: &str) -> Result<T, Cow<'static, str>> {
+        match self.default_value {
+            Some(value) if input.is_empty() => Ok(value),
+            _ => self.parse(input),
+        }
+    }
 }
 
-fn number_controller<T: NumLike + Sync + 'static + TypePath>(
-    mut query: Query<(Entity, &mut StringCursor), With<Number<T>>>,
-    mut input: EventReader<KeyboardInput>,
+impl<T: NumLike + TypePath> CurrentValue for Number<T> {
+    fn current_value(world: &World, id: Entity) -> Result<T, Error> {
+        let (Some(number), Some(text_state)) =
+            (world.get::<Self>(id), world.get::<StringCursor>(id))
+        else {
+            return Err(Error::InvalidInput);
+        };
+        let value = number
+            .value(&text_state.value)
+            .map_err(|_| Error::InvalidNumber)?;
+        number.bound(value).map_err(|_| Error::InvalidInput)
+    }
+}
+
+/// Show the default value as a placeholder unless one is given.
+fn number_placeholder<T: NumLike + TypePath + Sync + 'static>(
+    trigger: Trigger<OnInsert, Number<T>>,
+    query: Query<&Number<T>, Without<Placeholder>>,
     mut commands: Commands,
-    mut focus: FocusParam,
 ) {
-    for (id, mut text_state) in query.iter_mut() {
-        if !focus.is_focused(id) {
-            continue;
+    let id = trigger.target();
+    if let Ok(number) = query.get(id) {
+        if let Some(value) = number.default_value {
+            commands
+                .entity(id)
+                .try_insert(Placeholder(number.format.format(value).into()));
         }
-        for ev in input.read() {
-            if ev.state != ButtonState::Pressed {
-                continue;
-            }
-            // commands.entity(id).remove::<Feedback>();
-            match &ev.logical_key {
-                Key::Character(s) => {
-                    for c in s.chars() {
-                        if T::is_valid(c, &text_state) {
-                            text_state.insert(c);
-                        }
-                    }
+    }
+}
+
+fn number_controller(
+    query: Query<Entity, (With<NumberInput>, Without<Validating>)>,
+    mut events: EventReader<KeyboardInput>,
+    input: ContextInput,
+    mut commands: Commands,
+    focus: Focus,
+) {
+    let events: Vec<KeyboardInput> = events.read().cloned().collect();
+    for id in query.iter().filter(|id| focus.is_focused(*id)) {
+        let shift = input
+            .keys(id)
+            .any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+        for key in input.logical_keys(id, &events) {
+            // The typed observer handles the key in order with the others.
+            commands.trigger_targets(NumberKey { key, shift }, id);
+        }
+    }
+}
+
+fn number_key<T: NumLike + TypePath + Sync + 'static>(
+    trigger: Trigger<NumberKey>,
+    mut query: Query<(
+        &mut StringCursor,
+        &Number<T>,
+        Option<&Validator<T>>,
+        Option<&MapSubmit<T>>,
+    )>,
+    mut commands: Commands,
+    mut focus: FocusParam,
+) {
+    let id = trigger.target();
+    let Ok((mut text_state, number, validator, map)) = query.get_mut(id) else {
+        return;
+    };
+    let format = &number.format;
+    let before = text_state.value.clone();
+    // commands.entity(id).remove::<Feedback>();
+    match &trigger.event().key {
+        Key::Character(s) => {
+            for c in s.chars() {
+                if number.expression && expr::is_valid(format, c)
+                    || format.is_valid::<T>(c, &text_state)
+                {
+                    text_state.insert(c);
                 }
-                Key::Space => text_state.insert(' '),
-                Key::Backspace => text_state.backspace(),
-                Key::Delete => text_state.delete(),
-                Key::ArrowLeft => text_state.move_cursor(CursorDirection::Left),
-                Key::ArrowRight => text_state.move_cursor(CursorDirection::Right),
-                Key::Enter => {
-                    match T::from_str(&text_state.value) {
-                        Ok(number) => {
-                            commands.trigger_targets(Submit::new(Ok(number)), id);
+            }
+        }
+        Key::Space if number.expression => text_state.insert(' '),
+        Key::Backspace => text_state.backspace(),
+        Key::Delete => text_state.delete(),
+        Key::ArrowLeft => text_state.move_cursor(CursorDirection::Left),
+        Key::ArrowRight => text_state.move_cursor(CursorDirection::Right),
+        key @ (Key::ArrowUp | Key::ArrowDown) => {
+            let Some(value) = number
+                .parse(&text_state.value)
+                .ok()
+                .or(number.default_value)
+                .or(number.min)
+            else {
+                return;
+            };
+            let times = if trigger.event().shift { 10 } else { 1 };
+            let value = number.step_by(value, *key == Key::ArrowUp, times);
+            let value = format.format_like(value, &text_state.value);
+            text_state.set_value(&value);
+        }
+        Key::Enter => {
+            match number.value(&text_state.value) {
+                Ok(value) => match number.bound(value) {
+                    Ok(value) => {
+                        if validate(id, validator, &value, true, &mut commands)
+                            && submit::<Number<T>>(id, map, Ok(value), &mut commands)
+                        {
                             focus.block(id);
                             // focus.unfocus(id, true);
                             focus.move_focus_from(id);
                         }
-                        Err(_) => {
-                            // commands
-                            //     .trigger_targets(Submit::<T>(Err(Error::InvalidNumber)), id);
-                            // focus.block(id);
-                            commands.entity(id).try_insert(Feedback::warn(format!(
-                                "invalid number for {}",
-                                T::short_type_path()
-                            )));
-                        }
                     }
+                    Err(message) => {
+                        commands.entity(id).try_insert(Feedback::warn(message));
+                    }
+                },
+                Err(message) => {
+                    // commands
+                    //     .trigger_targets(Submit::<T>(Err(Error::InvalidNumber)), id);
+                    // focus.block(id);
+                    commands.entity(id).try_insert(Feedback::warn(message));
                 }
-                Key::Escape => {
-                    commands.trigger_targets(Submit::<String>::new(Err(Error::Cancel)), id);
-                    commands.entity(id).try_insert(Feedback::error("canceled"));
-                    focus.block(id);
-                    focus.move_focus_from(id);
-                    // focus.unfocus(id, false);
-                }
-                x => info!("Unhandled key {x:?}"),
             }
         }
+        Key::Escape => {
+            submit::<Number<T>>(id, map, Err(Error::Cancel), &mut commands);
+            commands.entity(id).try_insert(Feedback::error("canceled"));
+            focus.block(id);
+            focus.move_focus_from(id);
+            // focus.unfocus(id, false);
+        }
+        x => info!("Unhandled key {x:?}"),
+    }
+    if text_state.value == before {
+        return;
+    }
+    if let Ok(value) = number.parse(&text_state.value) {
+        validate_live(id, validator, &value, &mut commands);
+    }
+    if let Ok(value) = number
+        .value(&text_state.value)
+        .and_then(|value| number.bound(value))
+    {
+        value_changed::<Number<T>>(id, value, &mut commands);
     }
 }
 
 #[cfg(test)]
 mod test {
-
+    use super::Number;
     use crate::string_cursor::{ceil_char_boundary, floor_char_boundary};
+    use std::num::NonZeroU32;
+
+    #[cfg(not(feature = "focus"))]
+    #[test]
+    fn inserted_number() {
+        use crate::{
+            prelude::*,
+            string_cursor::StringCursor,
+            test::{app, press},
+        };
+        use bevy::{ecs::system::RunSystemOnce, input::keyboard::Key, prelude::*};
+        use std::sync::{Arc, Mutex};
+
+        let mut app = app();
+        app.register_number::<u128>();
+        let world = app.world_mut();
+        let id = world
+            .spawn((
+                Number::<u128>::new(),
+                StringCursor::default(),
+                Focusable::default(),
+                GlobalTransform::default(),
+            ))
+            .id();
+        world
+            .run_system_once(move |mut focus: Focus| focus.focus_on(id))
+            .unwrap();
+        let submits = Arc::new(Mutex::new(Vec::new()));
+        let sink = submits.clone();
+        world
+            .entity_mut(id)
+            .observe(move |mut trigger: Trigger<Submit<u128>>| {
+                sink.lock().unwrap().push(trigger.event_mut().take_result());
+            });
+        for (key_code, key) in [
+            (KeyCode::Digit4, Key::Character("4".into())),
+            (KeyCode::Digit2, Key::Character("2".into())),
+            (KeyCode::Enter, Key::Enter),
+        ] {
+            press(app.world_mut(), key_code, key);
+            app.update();
+        }
+        assert!(matches!(submits.lock().unwrap()[..], [Ok(42)]));
+    }
+
+    #[test]
+    fn test_bound() {
+        let number = Number::<u8>::new().min(1).max(10);
+        assert_eq!(number.bound(5), Ok(5));
+        assert!(number.bound(0).is_err());
+        assert!(number.bound(11).is_err());
+        let number = number.clamp();
+        assert_eq!(number.bound(0), Ok(1));
+        assert_eq!(number.bound(11), Ok(10));
+    }
+
+    #[test]
+    fn test_step() {
+        let number = Number::<u8>::new();
+        assert_eq!(number.step_by(5, true, 1), 6);
+        assert_eq!(number.step_by(5, false, 10), 0);
+        assert_eq!(number.step_by(250, true, 10), 255);
+
+        let number = Number::<i32>::new().min(-5).max(5).step(2);
+        assert_eq!(number.step_by(0, true, 1), 2);
+        assert_eq!(number.step_by(4, true, 1), 5);
+        assert_eq!(number.step_by(5, true, 1), 5);
+        let number = number.wrap();
+        assert_eq!(number.step_by(4, true, 1), 5);
+        assert_eq!(number.step_by(5, true, 1), -5);
+        assert_eq!(number.step_by(-5, false, 1), 5);
+
+        let number = Number::<f32>::new().step(0.5);
+        assert_eq!(number.step_by(1.0, true, 10), 6.0);
+    }
+
+    #[test]
+    fn test_step_non_zero() {
+        let one = NonZeroU32::MIN;
+        let number = Number::<NonZeroU32>::new();
+        assert_eq!(number.step_by(one, true, 2).get(), 3);
+        assert_eq!(number.step_by(one, false, 1), one);
+    }
 
     #[test]
     fn test_floor_char() {
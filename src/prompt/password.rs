@@ -17,12 +17,19 @@ pub(crate) fn plugin(_app: &mut App) {}
 /// | `Right`     | Move cursor right            |
 ///
 #[derive(Debug, Clone, Component, Reflect)]
+#[require(AnswerGuard<String>)]
 pub struct Password;
 
 unsafe impl Submitter for Password {
     type Out = String;
 }
 
+impl CurrentValue for Password {
+    fn current_value(world: &World, id: Entity) -> Result<String, Error> {
+        text_value(world, id)
+    }
+}
+
 impl Construct for Password {
     type Props = Cow<'static, str>;
 
@@ -37,6 +44,7 @@ impl Construct for Password {
             .entity(context.id)
             .insert(Prompt(props))
             .insert(input_state)
+            .insert(Preedit::default())
             .insert(Focusable::default());
         context.world.flush();
         Ok(Password)
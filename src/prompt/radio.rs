@@ -18,6 +18,8 @@ pub(crate) fn plugin(app: &mut App) {
         Update,
         (radio_controller, radio_group_controller).in_set(AskySet::Controller),
     );
+    #[cfg(any(feature = "color", feature = "ascii"))]
+    app.add_observer(radio_pointer);
 }
 
 impl Construct for Radio {
@@ -45,29 +47,40 @@ fn radio_controller(
     focus: FocusParam,
     mut query: Query<(Entity, &mut Radio, Option<&ChildOf>)>,
     child_query: Query<&Children>,
-    input: Res<ButtonInput<KeyCode>>,
+    validating: Query<(), With<Validating>>,
+    input: ContextInput,
     mut toggled: Local<Vec<(Entity, Entity)>>,
 ) {
-    if !input.any_just_pressed([KeyCode::Space, KeyCode::KeyH, KeyCode::KeyL]) {
-        return;
-    }
     toggled.clear();
     for (id, mut radio, parent) in query.iter_mut() {
         if !focus.is_focused(id) {
             continue;
         }
+        // Hold still while the group is being validated.
+        if parent.is_some_and(|p| validating.contains(p.parent())) {
+            continue;
+        }
+        let input = input.keys(id);
+        if !input.any_just_pressed([KeyCode::Space, KeyCode::KeyH, KeyCode::KeyL]) {
+            continue;
+        }
         let was_checked = radio.checked;
+        let mut checked = was_checked;
 
         if input.just_pressed(KeyCode::Space) {
-            radio.checked = !radio.checked;
+            checked = !checked;
         }
         if input.any_just_pressed([KeyCode::KeyL]) {
-            radio.checked = true;
+            checked = true;
         }
         if input.any_just_pressed([KeyCode::KeyH]) {
-            radio.checked = false;
+            checked = false;
+        }
+        // Only write real changes so the group sees them.
+        if checked != was_checked {
+            radio.checked = checked;
         }
-        if radio.checked && !was_checked {
+        if checked && !was_checked {
             // We've been checked and weren't checked before.
             if let Some(p) = parent {
                 toggled.push((id, p.parent()));
@@ -80,6 +93,42 @@ fn radio_controller(
                 continue;
             }
             if let Ok((_, mut radio, _)) = query.get_mut(*child) {
+                if radio.checked {
+                    radio.checked = false;
+                }
+            }
+        }
+    }
+}
+
+/// Check the radio pressed with the pointer and uncheck its siblings.
+#[cfg(any(feature = "color", feature = "ascii"))]
+fn radio_pointer(
+    trigger: Trigger<PointerSelect>,
+    mut query: Query<(&mut Radio, Option<&ChildOf>)>,
+    child_query: Query<&Children>,
+    validating: Query<(), With<Validating>>,
+) {
+    let id = trigger.target();
+    if *trigger.event() != PointerSelect::Check {
+        return;
+    }
+    let Ok((mut radio, parent)) = query.get_mut(id) else {
+        return;
+    };
+    if radio.checked || parent.is_some_and(|p| validating.contains(p.parent())) {
+        return;
+    }
+    radio.checked = true;
+    let Some(siblings) = parent.and_then(|p| child_query.get(p.parent()).ok()) else {
+        return;
+    };
+    for sibling in siblings {
+        if *sibling == id {
+            continue;
+        }
+        if let Ok((mut radio, _)) = query.get_mut(*sibling) {
+            if radio.checked {
                 radio.checked = false;
             }
         }
@@ -88,12 +137,27 @@ fn radio_controller(
 
 /// Parent of entities with [Radio] component
 #[derive(Component, Reflect, Default)]
+#[require(AnswerGuard<usize>)]
 pub struct RadioGroup;
 
 unsafe impl Submitter for RadioGroup {
     type Out = usize;
 }
 
+impl CurrentValue for RadioGroup {
+    fn current_value(world: &World, id: Entity) -> Result<usize, Error> {
+        world
+            .get::<Children>(id)
+            .and_then(|children| {
+                children
+                    .iter()
+                    .filter_map(|child| world.get::<Radio>(child))
+                    .position(|radio| radio.checked)
+            })
+            .ok_or(Error::InvalidInput)
+    }
+}
+
 impl Part for Radio {
     type Group = RadioGroup;
 }
@@ -120,21 +184,39 @@ impl Construct for RadioGroup {
 }
 
 fn radio_group_controller(
-    mut query: Query<(Entity, &Children), With<RadioGroup>>,
-    radios: Query<(Entity, &Radio)>,
+    mut query: Query<
+        (
+            Entity,
+            &Children,
+            Option<&Validator<usize>>,
+            Option<&MapSubmit<usize>>,
+        ),
+        (With<RadioGroup>, Without<Validating>),
+    >,
+    radios: Query<(Entity, Ref<Radio>)>,
     focus: FocusParam,
-    input: Res<ButtonInput<KeyCode>>,
+    input: ContextInput,
     mut commands: Commands,
 ) {
-    if !input.any_just_pressed([
-        KeyCode::Escape,
-        KeyCode::Enter,
-        KeyCode::ArrowDown,
-        KeyCode::ArrowUp,
-    ]) {
-        return;
+    for (id, children, validator, _) in &query {
+        if radios
+            .iter_many(children)
+            .any(|(_, radio)| radio.is_changed() && !radio.is_added())
+        {
+            if let Some(selection) = radios
+                .iter_many(children)
+                .position(|(_, radio)| radio.checked)
+            {
+                validate_live(id, validator, &selection, &mut commands);
+                value_changed::<RadioGroup>(id, selection, &mut commands);
+            }
+        }
     }
-    for (id, children) in query.iter_mut() {
+    for (id, children, validator, map) in query.iter_mut() {
+        let input = input.keys(id);
+        if !input.any_just_pressed([KeyCode::Escape, KeyCode::Enter]) {
+            continue;
+        }
         if let Some(_index) = radios
             .iter_many(children)
             .position(|(id, _)| focus.is_focused(id))
@@ -144,8 +226,10 @@ fn radio_group_controller(
                     .iter_many(children)
                     .position(|(_, radio)| radio.checked)
                 {
-                    // commands.trigger_targets(Submit::new(selection.ok_or(Error::InvalidInput)), id);
-                    commands.trigger_targets(Submit::new(Ok(selection)), id);
+                    if validate(id, validator, &selection, false, &mut commands) {
+                        // commands.trigger_targets(Submit::new(selection.ok_or(Error::InvalidInput)), id);
+                        submit::<RadioGroup>(id, map, Ok(selection), &mut commands);
+                    }
                 } else {
                     commands
                         .entity(id)
@@ -154,7 +238,7 @@ fn radio_group_controller(
             }
 
             if input.just_pressed(KeyCode::Escape) {
-                commands.trigger_targets(Submit::<usize>::new(Err(Error::Cancel)), id);
+                submit::<RadioGroup>(id, map, Err(Error::Cancel), &mut commands);
                 commands.entity(id).try_insert(Feedback::error("canceled"));
             }
         }
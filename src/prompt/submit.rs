@@ -0,0 +1,140 @@
+use crate::prelude::*;
+use bevy::ecs::{component::HookContext, world::DeferredWorld};
+use bevy::prelude::*;
+use std::marker::PhantomData;
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<Unanswered>();
+    #[cfg(debug_assertions)]
+    app.add_systems(Last, warn_unanswered_on_exit);
+}
+
+/// Marks a prompt that has not submitted yet
+///
+/// Prompts receive this component and lose it when they trigger [Submit].
+#[derive(Debug, Default, Component, Reflect)]
+pub struct Unanswered;
+
+/// Fires `Submit<T>` with [Error::Despawned] if its prompt is despawned
+/// unanswered.
+#[derive(Component)]
+#[component(on_despawn = despawn_unanswered::<T>)]
+#[require(Unanswered)]
+pub(crate) struct AnswerGuard<T: Send + Sync + 'static>(PhantomData<T>);
+
+impl<T: Send + Sync + 'static> Default for AnswerGuard<T> {
+    fn default() -> Self {
+        AnswerGuard(PhantomData)
+    }
+}
+
+fn despawn_unanswered<T: Send + Sync + 'static>(
+    mut world: DeferredWorld,
+    HookContext { entity, .. }: HookContext,
+) {
+    #[cfg(debug_assertions)]
+    warn!(
+        "{} despawned before it submitted",
+        describe(world.get::<Prompt>(entity), entity)
+    );
+    let map = world.get::<MapSubmit<T>>(entity).cloned();
+    submit_value(
+        entity,
+        map.as_ref(),
+        Err(Error::Despawned),
+        &mut world.commands(),
+    );
+}
+
+#[cfg(debug_assertions)]
+fn describe(prompt: Option<&Prompt>, id: Entity) -> String {
+    match prompt {
+        Some(prompt) => format!("Prompt {id} {:?}", prompt.0),
+        None => format!("Prompt {id}"),
+    }
+}
+
+/// Warn about prompts that never submitted when the app exits.
+#[cfg(debug_assertions)]
+fn warn_unanswered_on_exit(
+    mut exit: EventReader<AppExit>,
+    query: Query<(Entity, Option<&Prompt>), With<Unanswered>>,
+) {
+    if exit.read().next().is_none() {
+        return;
+    }
+    for (id, prompt) in &query {
+        warn!("{} never submitted", describe(prompt, id));
+    }
+}
+
+/// Trigger [Submit] for prompt `P` with `result`, mapped if there is a
+/// [MapSubmit].
+///
+/// `P` must be named, e.g., `submit::<Toggle>(..)`, and `result` must match
+/// its [Submitter::Out], so a prompt can't submit one type on success and
+/// another on cancel.
+///
+/// Returns false if mapping failed, which is shown as [Feedback] instead.
+pub(crate) fn submit<P: Submitter>(
+    id: Entity,
+    map: Option<&MapSubmit<P::Out>>,
+    result: Result<P::Out, Error>,
+    commands: &mut Commands,
+) -> bool
+where
+    P::Out: Send + Sync + 'static,
+{
+    submit_value(id, map, result, commands)
+}
+
+/// Trigger [Submit] with `result` once its prompt type no longer matters.
+pub(crate) fn submit_value<T: Send + Sync + 'static>(
+    id: Entity,
+    map: Option<&MapSubmit<T>>,
+    result: Result<T, Error>,
+    commands: &mut Commands,
+) -> bool {
+    match map {
+        Some(map) => {
+            if let Err(message) = map.submit(result, id, commands) {
+                commands.entity(id).try_insert(Feedback::error(message));
+                return false;
+            }
+        }
+        None => trigger_submit(id, result, commands),
+    }
+    commands
+        .entity(id)
+        .try_remove::<(AnswerGuard<T>, Unanswered)>();
+    true
+}
+
+/// Trigger [Submit] with `result`, or [Submit::Edited] if the prompt was
+/// answered already.
+pub(crate) fn trigger_submit<T: Send + Sync + 'static>(
+    id: Entity,
+    result: Result<T, Error>,
+    commands: &mut Commands,
+) {
+    commands.queue(move |world: &mut World| {
+        // A despawned prompt was unanswered.
+        let edit = world
+            .get_entity(id)
+            .is_ok_and(|entity| !entity.contains::<Unanswered>());
+        let submit = if edit {
+            Submit::edited(result)
+        } else {
+            Submit::new(result)
+        };
+        world.trigger_targets(submit, id);
+    });
+}
+
+/// Trigger [ValueChanged] for prompt `P` with `value`.
+pub(crate) fn value_changed<P: Submitter>(id: Entity, value: P::Out, commands: &mut Commands)
+where
+    P::Out: Send + Sync + 'static,
+{
+    commands.trigger_targets(ValueChanged(value), id);
+}
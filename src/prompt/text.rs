@@ -1,16 +1,44 @@
 use crate::{prelude::*, string_cursor::*};
 use bevy::{
-    input::{
-        keyboard::{Key, KeyboardInput},
-        ButtonState,
-    },
+    input::keyboard::{Key, KeyboardInput},
     prelude::*,
+    window::{Ime, PrimaryWindow},
 };
 use std::borrow::Cow;
 
 pub(crate) fn plugin(app: &mut App) {
     app.register_type::<StringCursor>()
-        .add_systems(Update, text_controller.in_set(AskySet::Controller));
+        .register_type::<Preedit>()
+        .add_systems(
+            Update,
+            (
+                text_controller::<TextField>,
+                text_controller::<Password>,
+                enable_ime,
+            )
+                .chain()
+                .in_set(AskySet::Controller),
+        );
+    // The default backend skips arrow keys per context itself.
+    #[cfg(feature = "focus")]
+    app.add_systems(
+        Update,
+        text_keyboard_nav
+            .after(enable_ime)
+            .in_set(AskySet::Controller),
+    );
+}
+
+/// Text being composed by an input method
+///
+/// Text prompts receive this component. It is not part of the value until the
+/// input method commits it.
+#[derive(Debug, Clone, Default, Component, Reflect)]
+pub struct Preedit {
+    /// Composing text
+    pub value: String,
+    /// Cursor begin and end byte positions within `value`, if shown
+    pub cursor: Option<(usize, usize)>,
 }
 
 /// Prompt to get one-line user input.
@@ -25,13 +53,30 @@ pub(crate) fn plugin(app: &mut App) {
 /// | `Left`      | Move cursor left             |
 /// | `Right`     | Move cursor right            |
 ///
+/// Add an [InputFilter] to restrict which characters are accepted. Input
+/// method (IME) composition is supported; see [Preedit].
 #[derive(Debug, Clone, Component, Reflect)]
+#[require(AnswerGuard<String>)]
 pub struct TextField;
 
 unsafe impl Submitter for TextField {
     type Out = String;
 }
 
+impl CurrentValue for TextField {
+    fn current_value(world: &World, id: Entity) -> Result<String, Error> {
+        text_value(world, id)
+    }
+}
+
+/// Read the value of a text prompt.
+pub(crate) fn text_value(world: &World, id: Entity) -> Result<String, Error> {
+    world
+        .get::<StringCursor>(id)
+        .map(|text_state| text_state.value.clone())
+        .ok_or(Error::InvalidInput)
+}
+
 impl Construct for TextField {
     type Props = Cow<'static, str>;
 
@@ -46,46 +91,84 @@ impl Construct for TextField {
             .entity(context.id)
             .insert(Prompt(props))
             .insert(input_state)
+            .insert(Preedit::default())
             .insert(Focusable::default());
         context.world.flush();
         Ok(TextField)
     }
 }
 
-fn text_controller(
-    mut focus: FocusParam,
-    mut query: Query<(Entity, &mut StringCursor), Or<(With<TextField>, With<Password>)>>,
-    mut input: EventReader<KeyboardInput>,
+/// Handle keys and input method events for text prompts of type `P`.
+fn text_controller<P: Submitter<Out = String> + Component>(
+    focus: FocusParam,
+    mut query: Query<
+        (
+            Entity,
+            &mut StringCursor,
+            Option<&mut Preedit>,
+            Option<&InputFilter>,
+            Option<&Validator<String>>,
+            Option<&MapSubmit<String>>,
+        ),
+        (With<P>, Without<Validating>),
+    >,
+    mut events: EventReader<KeyboardInput>,
+    mut ime: EventReader<Ime>,
+    input: ContextInput,
     mut commands: Commands,
 ) {
-    let mut any_focused_text = false;
-    for (id, mut text_state) in query.iter_mut() {
+    let events: Vec<KeyboardInput> = events.read().cloned().collect();
+    let ime: Vec<Ime> = ime.read().cloned().collect();
+    for (id, mut text_state, mut preedit, filter, validator, map) in query.iter_mut() {
         if !focus.is_focused(id) {
             continue;
         }
-        any_focused_text |= true;
-        for ev in input.read() {
-            if ev.state != ButtonState::Pressed {
+        let before = text_state.value.clone();
+        // The input method only serves contexts that type.
+        let ime = if input.typing(id) { &ime[..] } else { &[] };
+        for ev in ime {
+            match ev {
+                Ime::Preedit { value, cursor, .. } => {
+                    if let Some(preedit) = preedit.as_mut() {
+                        preedit.value.replace_range(.., value);
+                        preedit.cursor = *cursor;
+                    }
+                }
+                Ime::Commit { value, .. } => {
+                    for c in value.chars() {
+                        insert_filtered(id, c, &mut text_state, filter, &mut commands);
+                    }
+                }
+                Ime::Enabled { .. } | Ime::Disabled { .. } => {}
+            }
+        }
+        let composing = preedit.is_some_and(|preedit| !preedit.value.is_empty());
+        for key in input.logical_keys(id, &events) {
+            if composing {
+                // The input method consumes keys while composing.
                 continue;
             }
-            match &ev.logical_key {
+            match &key {
                 Key::Character(s) => {
                     for c in s.chars() {
-                        text_state.insert(c);
+                        insert_filtered(id, c, &mut text_state, filter, &mut commands);
                     }
                 }
-                Key::Space => text_state.insert(' '),
+                Key::Space => insert_filtered(id, ' ', &mut text_state, filter, &mut commands),
                 Key::Backspace => text_state.backspace(),
                 Key::Delete => text_state.delete(),
 
                 Key::ArrowLeft => text_state.move_cursor(CursorDirection::Left),
                 Key::ArrowRight => text_state.move_cursor(CursorDirection::Right),
                 Key::Enter => {
-                    commands.trigger_targets(Submit::new(Ok(text_state.value.clone())), id);
+                    if !validate(id, validator, &text_state.value, false, &mut commands) {
+                        continue;
+                    }
+                    submit::<P>(id, map, Ok(text_state.value.clone()), &mut commands);
                     // focus.block_and_move(id);
                 }
                 Key::Escape => {
-                    commands.trigger_targets(Submit::<String>::new(Err(Error::Cancel)), id);
+                    submit::<P>(id, map, Err(Error::Cancel), &mut commands);
                     // commands.entity(id).try_insert(Feedback::error("canceled"));
                     // focus.block(id);
                 }
@@ -94,6 +177,58 @@ fn text_controller(
                 }
             }
         }
+        if text_state.value != before {
+            validate_live(id, validator, &text_state.value, &mut commands);
+            value_changed::<P>(id, text_state.value.clone(), &mut commands);
+        }
     }
+}
+
+/// Turn off keyboard navigation while any [StringCursor] prompt has focus.
+///
+/// Those prompts use the arrow keys themselves. The `focus` feature's backend
+/// has one focus; the default one skips each context's focused text prompt.
+#[cfg(feature = "focus")]
+fn text_keyboard_nav(mut focus: FocusParam, query: Query<Entity, With<StringCursor>>) {
+    let any_focused_text = query.iter().any(|id| focus.is_focused(id));
     focus.set_keyboard_nav(!any_focused_text);
 }
+
+fn insert_filtered(
+    id: Entity,
+    ch: char,
+    text_state: &mut StringCursor,
+    filter: Option<&InputFilter>,
+    commands: &mut Commands,
+) {
+    match filter.map(|filter| filter.check(ch, text_state)) {
+        Some(Err(reason)) => {
+            if filter.is_some_and(|filter| filter.warn) {
+                commands.entity(id).try_insert(Feedback::warn(reason));
+            }
+        }
+        _ => text_state.insert(ch),
+    }
+}
+
+/// Enable the input method on the primary window while a text prompt has focus.
+fn enable_ime(
+    focus: Focus,
+    mut query: Query<(Entity, &mut Preedit)>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let mut any_focused_text = false;
+    for (id, mut preedit) in &mut query {
+        if focus.is_focused(id) {
+            any_focused_text = true;
+        } else if !preedit.value.is_empty() {
+            // Composition is abandoned when focus moves.
+            *preedit = Preedit::default();
+        }
+    }
+    for mut window in &mut windows {
+        if window.ime_enabled != any_focused_text {
+            window.ime_enabled = any_focused_text;
+        }
+    }
+}
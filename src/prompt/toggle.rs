@@ -4,6 +4,7 @@ use std::borrow::Cow;
 
 /// Toggles between two named options
 #[derive(Component, Clone, Reflect)]
+#[require(AnswerGuard<usize>)]
 pub struct Toggle {
     /// Prompt
     pub message: Cow<'static, str>,
@@ -26,6 +27,19 @@ impl OptionPrompt for Toggle {
     }
 }
 
+unsafe impl Submitter for Toggle {
+    type Out = usize;
+}
+
+impl CurrentValue for Toggle {
+    fn current_value(world: &World, id: Entity) -> Result<usize, Error> {
+        world
+            .get::<Self>(id)
+            .map(|toggle| toggle.index)
+            .ok_or(Error::InvalidInput)
+    }
+}
+
 impl Toggle {
     /// Make a new toggle
     pub fn new<T: Into<Cow<'static, str>>>(
@@ -43,6 +57,8 @@ impl Toggle {
 
 pub(crate) fn plugin(app: &mut App) {
     app.add_systems(Update, toggle_controller.in_set(AskySet::Controller));
+    #[cfg(any(feature = "color", feature = "ascii"))]
+    app.add_observer(toggle_pointer);
 }
 
 impl Construct for Toggle {
@@ -64,15 +80,24 @@ impl Construct for Toggle {
 }
 
 fn toggle_controller(
-    mut query: Query<(Entity, &mut Toggle)>,
-    input: Res<ButtonInput<KeyCode>>,
+    mut query: Query<
+        (
+            Entity,
+            &mut Toggle,
+            Option<&Validator<usize>>,
+            Option<&MapSubmit<usize>>,
+        ),
+        Without<Validating>,
+    >,
+    input: ContextInput,
     mut commands: Commands,
     mut focus: FocusParam,
 ) {
-    for (id, mut toggle) in query.iter_mut() {
+    for (id, mut toggle, validator, map) in query.iter_mut() {
         if !focus.is_focused(id) {
             continue;
         }
+        let input = input.keys(id);
         if input.any_just_pressed([
             KeyCode::KeyH,
             KeyCode::ArrowLeft,
@@ -81,19 +106,26 @@ fn toggle_controller(
             KeyCode::Enter,
             KeyCode::Escape,
         ]) {
+            let before = toggle.index;
             if input.any_just_pressed([KeyCode::KeyH, KeyCode::ArrowLeft]) {
                 toggle.index = 0;
             }
             if input.any_just_pressed([KeyCode::KeyL, KeyCode::ArrowRight]) {
                 toggle.index = 1;
             }
-            if input.just_pressed(KeyCode::Enter) {
-                commands.trigger_targets(Submit::new(Ok(toggle.index)), id);
+            if toggle.index != before {
+                validate_live(id, validator, &toggle.index, &mut commands);
+                value_changed::<Toggle>(id, toggle.index, &mut commands);
+            }
+            if input.just_pressed(KeyCode::Enter)
+                && validate(id, validator, &toggle.index, true, &mut commands)
+                && submit::<Toggle>(id, map, Ok(toggle.index), &mut commands)
+            {
                 focus.block_and_move(id);
             }
 
             if input.just_pressed(KeyCode::Escape) {
-                commands.trigger_targets(Submit::<bool>::new(Err(Error::Cancel)), id);
+                submit::<Toggle>(id, map, Err(Error::Cancel), &mut commands);
                 focus.move_focus_from(id);
                 // focus.unfocus(id, false);
                 commands.entity(id).try_insert(Feedback::error("canceled"));
@@ -101,3 +133,23 @@ fn toggle_controller(
         }
     }
 }
+
+/// Select the option pressed with the pointer.
+#[cfg(any(feature = "color", feature = "ascii"))]
+fn toggle_pointer(
+    trigger: Trigger<PointerSelect>,
+    mut query: Query<(&mut Toggle, Option<&Validator<usize>>), Without<Validating>>,
+    mut commands: Commands,
+) {
+    let id = trigger.target();
+    let (PointerSelect::Option(index), Ok((mut toggle, validator))) =
+        (*trigger.event(), query.get_mut(id))
+    else {
+        return;
+    };
+    if toggle.index != index {
+        toggle.index = index;
+        validate_live(id, validator, &toggle.index, &mut commands);
+        value_changed::<Toggle>(id, toggle.index, &mut commands);
+    }
+}
@@ -0,0 +1,233 @@
+use crate::{construct::*, prelude::*};
+use bevy::prelude::*;
+#[cfg(feature = "async")]
+use bevy_defer::{AsyncCommandsExtension, AsyncWorld};
+use std::{borrow::Cow, sync::Arc};
+#[cfg(feature = "async")]
+use std::{future::Future, pin::Pin};
+
+/// Function for [Validator]
+pub type ValidateFn<T> = dyn Fn(&T) -> Result<(), Cow<'static, str>> + Send + Sync;
+
+/// Future of an asynchronous [Validator] check
+#[cfg(feature = "async")]
+pub type ValidateFuture = Pin<Box<dyn Future<Output = Result<(), Cow<'static, str>>>>>;
+
+/// Asynchronous function for [Validator]
+#[cfg(feature = "async")]
+pub type AsyncValidateFn<T> = dyn Fn(T) -> ValidateFuture + Send + Sync;
+
+/// Marks a prompt whose value is being checked asynchronously
+///
+/// The prompt ignores input until the check finishes.
+#[derive(Debug, Component, Reflect)]
+pub struct Validating;
+
+/// Checks a prompt's value before it is submitted
+///
+/// Add to a prompt entity with the prompt's output type, e.g.,
+/// `Validator<String>` for a [TextField]. On `Enter` the value is checked
+/// and a failure is shown as an error [Feedback] instead of submitting.
+///
+/// ```
+/// # use bevy_asky::prelude::*;
+/// let validator = Validator::<String>::new(|name| {
+///     if name.trim().is_empty() {
+///         Err("name required".into())
+///     } else {
+///         Ok(())
+///     }
+/// })
+/// .live();
+/// ```
+///
+/// With the "async" feature, a validator may also await a check, e.g., that
+/// a save name isn't taken already. It runs on submit after the synchronous
+/// check passes. The prompt is marked [Validating] and shows "checking…"
+/// until it finishes, then submits only if the value is valid.
+#[derive(Component)]
+pub struct Validator<T: 'static> {
+    check: Arc<ValidateFn<T>>,
+    #[cfg(feature = "async")]
+    check_async: Option<Arc<AsyncValidateFn<T>>>,
+    /// Validate on every edit rather than only on submit.
+    ///
+    /// Only the synchronous check is live.
+    pub live: bool,
+}
+
+impl<T> Clone for Validator<T> {
+    fn clone(&self) -> Self {
+        Validator {
+            check: self.check.clone(),
+            #[cfg(feature = "async")]
+            check_async: self.check_async.clone(),
+            live: self.live,
+        }
+    }
+}
+
+impl<T> Validator<T> {
+    /// Make a validator from a function.
+    pub fn new(
+        check: impl Fn(&T) -> Result<(), Cow<'static, str>> + Send + Sync + 'static,
+    ) -> Self {
+        Validator {
+            check: Arc::new(check),
+            #[cfg(feature = "async")]
+            check_async: None,
+            live: false,
+        }
+    }
+
+    /// Make a validator from an asynchronous function.
+    #[cfg(feature = "async")]
+    pub fn new_async<F>(check: impl Fn(T) -> F + Send + Sync + 'static) -> Self
+    where
+        F: Future<Output = Result<(), Cow<'static, str>>> + 'static,
+    {
+        Validator::new(|_| Ok(())).check_async(check)
+    }
+
+    /// Also await an asynchronous check on submit.
+    #[cfg(feature = "async")]
+    pub fn check_async<F>(mut self, check: impl Fn(T) -> F + Send + Sync + 'static) -> Self
+    where
+        F: Future<Output = Result<(), Cow<'static, str>>> + 'static,
+    {
+        self.check_async = Some(Arc::new(move |value| -> ValidateFuture {
+            Box::pin(check(value))
+        }));
+        self
+    }
+
+    /// Validate on every edit.
+    pub fn live(mut self) -> Self {
+        self.live = true;
+        self
+    }
+
+    /// Check `value`, returning the reason it is invalid otherwise.
+    pub fn validate(&self, value: &T) -> Result<(), Cow<'static, str>> {
+        (self.check)(value)
+    }
+}
+
+impl<T: Send + Sync + 'static> Construct for Validator<T> {
+    type Props = Validator<T>;
+    fn construct(
+        _context: &mut ConstructContext,
+        props: Self::Props,
+    ) -> Result<Self, ConstructError> {
+        Ok(props)
+    }
+}
+
+/// Check `value` before submitting it.
+///
+/// Returns true if it may be submitted now. Otherwise shows the error as
+/// [Feedback], or starts an asynchronous check which submits `value` itself
+/// if it passes. `block` says whether to then block focus and move on.
+#[cfg_attr(not(feature = "async"), allow(unused_variables))]
+pub(crate) fn validate<T: Clone + Send + Sync + 'static>(
+    id: Entity,
+    validator: Option<&Validator<T>>,
+    value: &T,
+    block: bool,
+    commands: &mut Commands,
+) -> bool {
+    let Some(validator) = validator else {
+        return true;
+    };
+    if let Err(message) = validator.validate(value) {
+        commands.entity(id).try_insert(Feedback::error(message));
+        return false;
+    }
+    #[cfg(feature = "async")]
+    if let Some(check) = validator.check_async.clone() {
+        let value = value.clone();
+        commands
+            .entity(id)
+            .try_insert((Validating, Feedback::info("checking…")));
+        commands.spawn_task(move || async move {
+            let result = check(value.clone()).await;
+            AsyncWorld::new().apply_command(move |world: &mut World| {
+                finish_validation(world, id, value, result, block)
+            });
+            Ok(())
+        });
+        return false;
+    }
+    true
+}
+
+/// Submit `value` if the asynchronous check passed.
+#[cfg(feature = "async")]
+fn finish_validation<T: Send + Sync + 'static>(
+    world: &mut World,
+    id: Entity,
+    value: T,
+    result: Result<(), Cow<'static, str>>,
+    block: bool,
+) {
+    let Ok(mut entity) = world.get_entity_mut(id) else {
+        return;
+    };
+    entity.remove::<Validating>();
+    match result {
+        Ok(()) => {
+            entity.insert(Feedback::none());
+            let map = entity.get::<MapSubmit<T>>().cloned();
+            let submitted = submit_value(id, map.as_ref(), Ok(value), &mut world.commands());
+            world.flush();
+            if submitted && block {
+                let _ = world.run_system_cached_with(block_and_move, id);
+            }
+        }
+        Err(message) => {
+            entity.insert(Feedback::error(message));
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+fn block_and_move(In(id): In<Entity>, mut focus: FocusParam) {
+    focus.block_and_move(id);
+}
+
+/// Check `value` after an edit if the validator is live.
+pub(crate) fn validate_live<T>(
+    id: Entity,
+    validator: Option<&Validator<T>>,
+    value: &T,
+    commands: &mut Commands,
+) {
+    let Some(validator) = validator.filter(|validator| validator.live) else {
+        return;
+    };
+    let feedback = match validator.validate(value) {
+        Ok(()) => Feedback::none(),
+        Err(message) => Feedback::error(message),
+    };
+    commands.entity(id).try_insert(feedback);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn validate() {
+        let validator = Validator::<u32>::new(|x| {
+            if x % 2 == 0 {
+                Ok(())
+            } else {
+                Err("must be even".into())
+            }
+        });
+        assert!(!validator.live);
+        assert!(validator.validate(&2).is_ok());
+        assert_eq!(validator.validate(&3), Err("must be even".into()));
+        assert!(validator.live().live);
+    }
+}
@@ -76,6 +76,54 @@ impl StringCursor {
     }
 }
 
+/// A horizontally scrolled window of a [StringCursor]'s value
+///
+/// See [StringCursor::scroll_window].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScrollWindow {
+    /// Byte index of the first visible character
+    pub start: usize,
+    /// Byte index after the last visible character
+    pub end: usize,
+    /// Are there hidden characters before `start`?
+    pub clipped_start: bool,
+    /// Are there hidden characters after `end`?
+    pub clipped_end: bool,
+}
+
+impl StringCursor {
+    /// Return a window of `width` characters that contains the cursor.
+    ///
+    /// `scroll` is the character offset of the window. It is updated to keep
+    /// the cursor visible and should be kept between calls. The cursor at the
+    /// end of the value occupies a character too.
+    pub fn scroll_window(&self, scroll: &mut usize, width: usize) -> ScrollWindow {
+        let width = width.max(1);
+        let cursor = self.value[..self.index].chars().count();
+        let len = self.value.chars().count();
+        if cursor < *scroll {
+            *scroll = cursor;
+        } else if cursor >= *scroll + width {
+            *scroll = cursor + 1 - width;
+        }
+        // Don't leave empty space when the value shrinks.
+        *scroll = (*scroll).min((len + 1).saturating_sub(width));
+        let char_to_byte = |c: usize| {
+            self.value
+                .char_indices()
+                .nth(c)
+                .map(|(i, _)| i)
+                .unwrap_or(self.value.len())
+        };
+        ScrollWindow {
+            start: char_to_byte(*scroll),
+            end: char_to_byte(*scroll + width),
+            clipped_start: *scroll > 0,
+            clipped_end: *scroll + width < len,
+        }
+    }
+}
+
 /// Return the current or prior character boundary.
 pub fn floor_char_boundary(s: &str, mut i: usize) -> usize {
     if i > s.len() {
@@ -131,4 +179,31 @@ mod test {
         assert_eq!(ceil_char_boundary(s, 26), 26);
         assert_eq!(ceil_char_boundary(s, 27), 26);
     }
+
+    #[test]
+    fn test_scroll_window() {
+        let mut cursor = StringCursor::default();
+        cursor.set_value("abcdef");
+        let mut scroll = 0;
+        // Cursor at the end needs a character of its own.
+        let window = cursor.scroll_window(&mut scroll, 4);
+        assert_eq!(scroll, 3);
+        assert_eq!(&cursor.value[window.start..window.end], "def");
+        assert!(window.clipped_start);
+        assert!(!window.clipped_end);
+
+        cursor.index = 1;
+        let window = cursor.scroll_window(&mut scroll, 4);
+        assert_eq!(scroll, 1);
+        assert_eq!(&cursor.value[window.start..window.end], "bcde");
+        assert!(window.clipped_start);
+        assert!(window.clipped_end);
+
+        cursor.set_value("ab");
+        let window = cursor.scroll_window(&mut scroll, 4);
+        assert_eq!(scroll, 0);
+        assert_eq!(&cursor.value[window.start..window.end], "ab");
+        assert!(!window.clipped_start);
+        assert!(!window.clipped_end);
+    }
 }
@@ -0,0 +1,35 @@
+//! Harness shared by tests
+use crate::AskyPlugin;
+use bevy::{
+    input::{
+        keyboard::{Key, KeyboardInput},
+        ButtonState,
+    },
+    prelude::*,
+    window::Ime,
+};
+
+/// Make an app with asky and the input it reads, but no window.
+pub(crate) fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AskyPlugin))
+        .init_resource::<ButtonInput<KeyCode>>()
+        .add_event::<KeyboardInput>()
+        .add_event::<Ime>();
+    app
+}
+
+/// Press a key, releasing any others, for the next update.
+pub(crate) fn press(world: &mut World, key_code: KeyCode, logical_key: Key) {
+    let mut input = world.resource_mut::<ButtonInput<KeyCode>>();
+    input.reset_all();
+    input.press(key_code);
+    world.send_event(KeyboardInput {
+        key_code,
+        logical_key,
+        state: ButtonState::Pressed,
+        text: None,
+        repeat: false,
+        window: Entity::PLACEHOLDER,
+    });
+}
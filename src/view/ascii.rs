@@ -1,6 +1,7 @@
 //! Use ascii text
+use super::pointer::{self, chars_before, pointer_position, set_hover, text_hit, HoverQuery};
 use crate::{prelude::*, string_cursor::*};
-use bevy::prelude::*;
+use bevy::{prelude::*, text::TextLayoutInfo, window::PrimaryWindow};
 use std::fmt::Write;
 
 #[repr(u8)]
@@ -13,6 +14,9 @@ enum ViewPart {
     Feedback = 5,
 }
 
+/// Background color under the pointer
+const HOVER: Color = Color::srgba(1.0, 1.0, 1.0, 0.1);
+
 /// Marker for ascii views
 #[derive(Component, Default)]
 pub struct View;
@@ -52,13 +56,94 @@ pub fn plugin(app: &mut App) {
             prompt_view,
             confirm_view,
             text_view,
+            duration_view,
             password_view,
             toggle_view,
             feedback_view,
             clear_feedback::<StringCursor>,
             clear_feedback::<Toggle>,
+            hover_view,
+            pointer_view.after(pointer::click_to_focus),
         ),
-    );
+    )
+    .add_observer(focus_changed::<FocusGained>)
+    .add_observer(focus_changed::<FocusLost>);
+}
+
+/// Mark a view changed when its focus changes so it is drawn again.
+fn focus_changed<E: Event>(trigger: Trigger<E>, mut views: Query<&mut View>) {
+    if let Ok(mut view) = views.get_mut(trigger.target()) {
+        view.set_changed();
+    }
+}
+
+/// Turn a press on part of a focused view into a [PointerSelect].
+fn pointer_view(
+    query: Query<
+        (
+            Entity,
+            &Interaction,
+            &ComputedNode,
+            &GlobalTransform,
+            &TextLayoutInfo,
+            Has<Checkbox>,
+            Has<Radio>,
+            Has<Confirm>,
+            Option<&Toggle>,
+            Option<&StringCursor>,
+        ),
+        (With<View>, Changed<Interaction>),
+    >,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    focus: Focus,
+    mut writer: TextUiWriter,
+    mut commands: Commands,
+) {
+    let Some(point) = pointer_position(&windows) else {
+        return;
+    };
+    for (id, interaction, node, transform, layout, checkbox, radio, confirm, toggle, text_state) in
+        &query
+    {
+        if *interaction != Interaction::Pressed || !focus.is_focused(id) {
+            continue;
+        }
+        if checkbox || radio {
+            commands.trigger_targets(PointerSelect::Check, id);
+            continue;
+        }
+        let Some((span, byte)) = text_hit(point, node, transform, layout) else {
+            continue;
+        };
+        // Glyphs index bytes by line; the view is one line.
+        let start: usize = (0..span).map(|i| writer.text(id, i).len()).sum();
+        let byte = byte.saturating_sub(start);
+        let select = if span == ViewPart::Options as usize {
+            // Options read " no/YES" or " [a] _b_".
+            let split = if confirm {
+                Some(" no".len())
+            } else {
+                toggle.map(|toggle| toggle.options[0].len() + " []".len())
+            };
+            split.map(|split| PointerSelect::Option((byte > split) as usize))
+        } else if span == ViewPart::Answer as usize {
+            text_state.map(|text_state| {
+                let k = chars_before(&writer.text(id, span), byte);
+                let index = text_state.value[..text_state.index].chars().count();
+                PointerSelect::MoveCursor(k as isize - index as isize)
+            })
+        } else {
+            None
+        };
+        if let Some(select) = select {
+            commands.trigger_targets(select, id);
+        }
+    }
+}
+
+/// Show the hover color behind a view under the pointer.
+fn hover_view(query: HoverQuery<View>, mut commands: Commands) {
+    set_hover(&query, HOVER, &mut commands);
 }
 
 pub(crate) fn confirm_view(
@@ -67,7 +152,7 @@ pub(crate) fn confirm_view(
         (
             With<View>,
             With<Text>,
-            Or<(Changed<Focusable>, Changed<Confirm>)>,
+            Or<(Changed<View>, Changed<Confirm>)>,
         ),
     >,
     mut writer: TextUiWriter,
@@ -102,15 +187,23 @@ pub(crate) fn checkbox_view(
     }
 }
 
+/// Mark the focused view with `>` and an answered [Editable] one with `+`.
 pub(crate) fn focus_view(
-    mut query: Query<Entity, (With<View>, With<Text>, Changed<Focusable>)>,
+    mut query: Query<(Entity, Has<Editable>, Has<Unanswered>), (With<Text>, Changed<View>)>,
     focus: Focus,
     mut writer: TextUiWriter,
 ) {
-    for id in query.iter_mut() {
-        writer
-            .text(id, ViewPart::Focus as usize)
-            .replace_range(.., if focus.is_focused(id) { "> " } else { "  " });
+    for (id, editable, unanswered) in query.iter_mut() {
+        writer.text(id, ViewPart::Focus as usize).replace_range(
+            ..,
+            if focus.is_focused(id) {
+                "> "
+            } else if editable && !unanswered {
+                "+ "
+            } else {
+                "  "
+            },
+        );
     }
 }
 
@@ -125,11 +218,15 @@ pub(crate) fn feedback_view(
     }
 }
 
+/// Clear feedback when `T` changes unless the feedback is just as new, e.g.,
+/// from a live [Validator].
 pub(crate) fn clear_feedback<T: Component>(
     mut query: Query<&mut Feedback, (With<View>, Changed<T>)>,
 ) {
     for mut feedback in query.iter_mut() {
-        feedback.clear();
+        if !feedback.is_changed() {
+            feedback.clear();
+        }
     }
 }
 
@@ -158,11 +255,7 @@ pub(crate) fn radio_view(
 pub(crate) fn toggle_view(
     mut query: Query<
         (Entity, &Toggle),
-        (
-            With<View>,
-            With<Text>,
-            Or<(Changed<Focusable>, Changed<Toggle>)>,
-        ),
+        (With<View>, With<Text>, Or<(Changed<View>, Changed<Toggle>)>),
     >,
     focus: Focus,
     mut writer: TextUiWriter,
@@ -205,6 +298,27 @@ pub(crate) fn text_view(
     }
 }
 
+pub(crate) fn duration_view(
+    query: Query<
+        (Entity, &StringCursor),
+        (
+            With<View>,
+            With<Text>,
+            With<DurationPrompt>,
+            Changed<StringCursor>,
+        ),
+    >,
+    mut writer: TextUiWriter,
+) {
+    for (id, text_state) in &query {
+        let mut text = writer.text(id, ViewPart::Options as usize);
+        text.clear();
+        if let Ok(duration) = parse_duration(&text_state.value) {
+            let _ = write!(text, " = {}", format_duration(duration));
+        }
+    }
+}
+
 pub(crate) fn password_view(
     mut query: Query<
         (Entity, &StringCursor, Option<&Placeholder>),
@@ -1,6 +1,8 @@
 //! Uses colored text
+use super::pointer::{self, chars_before, pointer_position, set_hover, text_hit, HoverQuery};
 use crate::{construct::*, prelude::*, string_cursor::*};
-use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy::{ecs::system::SystemParam, prelude::*, text::TextLayoutInfo, window::PrimaryWindow};
+use std::fmt::Write;
 
 const PADDING: Val = Val::Px(5.);
 
@@ -16,8 +18,10 @@ pub struct View;
 ///     - TextSpan, Question
 ///     - TextSpan, Answer
 ///     - Text, PreCursor, Toggle0
+///     - Text, Preedit
 ///     - Text, Cursor
 ///     - Text, PostCursor, Toggle1
+///     - Text, Preview
 ///     - Text, Feedback
 #[derive(Debug, Component)]
 #[repr(u8)]
@@ -42,6 +46,10 @@ pub enum ViewPart {
     PostCursor = 8,
     /// Feedback if any
     Feedback = 9,
+    /// Input method composing text at the cursor
+    Preedit = 10,
+    /// Live preview of the parsed value
+    Preview = 11,
 }
 
 /// Writes to part of the view
@@ -63,9 +71,11 @@ impl ViewWriter<'_, '_> {
                 span_children[part as usize - 1]
             }
             PreCursor | Toggle0 => children[1],
-            Cursor => children[2],
-            PostCursor | Toggle1 => children[3],
-            Feedback => children[4],
+            Preedit => children[2],
+            Cursor => children[3],
+            PostCursor | Toggle1 => children[4],
+            Preview => children[5],
+            Feedback => children[6],
         }
     }
 
@@ -76,9 +86,11 @@ impl ViewWriter<'_, '_> {
         match part {
             Focus | PreQuestion | Question | Answer => self.writer.text(children[0], part as usize),
             PreCursor | Toggle0 => self.writer.text(children[1], 0),
-            Cursor => self.writer.text(children[2], 0),
-            PostCursor | Toggle1 => self.writer.text(children[3], 0),
-            Feedback => self.writer.text(children[4], 0),
+            Preedit => self.writer.text(children[2], 0),
+            Cursor => self.writer.text(children[3], 0),
+            PostCursor | Toggle1 => self.writer.text(children[4], 0),
+            Preview => self.writer.text(children[5], 0),
+            Feedback => self.writer.text(children[6], 0),
         }
     }
 
@@ -91,13 +103,47 @@ impl ViewWriter<'_, '_> {
                 self.writer.color(children[0], part as usize)
             }
             PreCursor | Toggle0 => self.writer.color(children[1], 0),
-            Cursor => self.writer.color(children[2], 0),
-            PostCursor | Toggle1 => self.writer.color(children[3], 0),
-            Feedback => self.writer.color(children[4], 0),
+            Preedit => self.writer.color(children[2], 0),
+            Cursor => self.writer.color(children[3], 0),
+            PostCursor | Toggle1 => self.writer.color(children[4], 0),
+            Preview => self.writer.color(children[5], 0),
+            Feedback => self.writer.color(children[6], 0),
         }
     }
 }
 
+/// Marks clipped text in a [SingleLine] view.
+const ELLIPSIS: &str = "…";
+
+/// Show a text prompt on one line that scrolls horizontally
+///
+/// At most `width` characters of the value are shown. The window scrolls to
+/// keep the cursor visible, and an ellipsis marks each clipped side.
+#[derive(Debug, Component, Reflect)]
+pub struct SingleLine {
+    /// Number of visible characters
+    pub width: usize,
+    scroll: usize,
+}
+
+impl SingleLine {
+    /// Make a single line viewport `width` characters wide.
+    pub fn new(width: usize) -> Self {
+        SingleLine { width, scroll: 0 }
+    }
+}
+
+impl Construct for SingleLine {
+    type Props = usize;
+
+    fn construct(
+        _context: &mut ConstructContext,
+        props: Self::Props,
+    ) -> Result<Self, ConstructError> {
+        Ok(SingleLine::new(props))
+    }
+}
+
 /// This node is a cursor.
 #[derive(Debug, Component, Reflect)]
 pub struct Cursor;
@@ -113,7 +159,10 @@ impl Construct for View {
         context: &mut ConstructContext,
         _props: Self::Props,
     ) -> Result<Self, ConstructError> {
-        let highlight = context.world.resource::<Palette>().highlight;
+        let palette = context.world.resource::<Palette>();
+        let highlight = palette.highlight;
+        let text_color = palette.text_color;
+        let lowlight = palette.lowlight;
         if let Ok(mut eref) = context.world.get_entity_mut(context.id) {
             if !eref.contains::<Node>() {
                 eref.insert(Node {
@@ -129,8 +178,24 @@ impl Construct for View {
                         parent.spawn(TextSpan::default()); // Answer
                     });
                 node.spawn(Text::default()); // PreCursor, Toggle0
+                node.spawn((
+                    Text::default(),
+                    Node {
+                        border: UiRect::bottom(Val::Px(1.0)),
+                        ..default()
+                    },
+                    BorderColor(text_color.into()),
+                )); // Preedit
                 node.spawn(Text::default()); // Cursor
                 node.spawn(Text::default()); // PostCursor, Toggle1
+                node.spawn((
+                    Text::default(),
+                    TextColor(lowlight.into()),
+                    Node {
+                        margin: UiRect::left(PADDING),
+                        ..default()
+                    },
+                )); // Preview
                 node.spawn(Text::default()); // Feedback
             });
         }
@@ -156,6 +221,8 @@ pub struct Palette {
     pub answer: Srgba,
     /// Lowlight color
     pub lowlight: Srgba,
+    /// Background color under the pointer
+    pub hover: Srgba,
 }
 
 impl Default for Palette {
@@ -167,6 +234,7 @@ impl Default for Palette {
             complete: Srgba::hex("94DD8D").unwrap(),
             answer: Srgba::hex("FFB9E8").unwrap(),
             lowlight: Srgba::hex("5A607A").unwrap(),
+            hover: Srgba::hex("2A2F45").unwrap(),
         }
     }
 }
@@ -183,6 +251,7 @@ pub fn plugin_no_focus(app: &mut App) {
         .register_type::<Cursor>()
         .register_type::<CursorBlink>()
         .register_type::<Palette>()
+        .register_type::<SingleLine>()
         .add_systems(
             Update,
             (
@@ -191,6 +260,11 @@ pub fn plugin_no_focus(app: &mut App) {
                 prompt_view,
                 text_view::<Without<Password>>,
                 opaque_view::<With<Password>>,
+                preedit_view,
+                duration_view,
+                answer_view,
+                ime_position,
+                single_line_layout,
                 option_view::<Confirm>,
                 option_view::<Toggle>,
                 blink_cursor,
@@ -204,6 +278,17 @@ pub fn plugin_no_focus(app: &mut App) {
             )
                 .in_set(AskySet::View),
         )
+        .add_systems(
+            Update,
+            (
+                pointer_view
+                    .in_set(AskySet::Controller)
+                    .after(pointer::click_to_focus),
+                hover_view.in_set(AskySet::View),
+            ),
+        )
+        .add_observer(focus_changed::<FocusGained>)
+        .add_observer(focus_changed::<FocusLost>)
         .insert_resource(CursorBlink(Timer::from_seconds(
             1.0 / 3.0,
             TimerMode::Repeating,
@@ -211,6 +296,91 @@ pub fn plugin_no_focus(app: &mut App) {
         .insert_resource(Palette::default());
 }
 
+/// Turn a press on part of a focused view into a [PointerSelect].
+fn pointer_view(
+    query: Query<
+        (
+            Entity,
+            &Interaction,
+            Has<Checkbox>,
+            Has<Radio>,
+            Has<StringCursor>,
+        ),
+        (With<View>, Changed<Interaction>),
+    >,
+    options: Query<(), Or<(With<Confirm>, With<Toggle>)>>,
+    nodes: Query<(&ComputedNode, &GlobalTransform, &TextLayoutInfo)>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    focus: Focus,
+    mut writer: ViewWriter,
+    mut commands: Commands,
+) {
+    let Some(point) = pointer_position(&windows) else {
+        return;
+    };
+    for (id, interaction, checkbox, radio, text) in &query {
+        if *interaction != Interaction::Pressed || !focus.is_focused(id) {
+            continue;
+        }
+        // Outer `None` if the part is not pressed, inner `None` if it has no glyphs.
+        let hit = |writer: &mut ViewWriter, part: ViewPart| -> Option<Option<usize>> {
+            let (node, transform, layout) = nodes.get(writer.entity(id, part)).ok()?;
+            contains(point, node, transform)
+                .then(|| text_hit(point, node, transform, layout).map(|(_, byte)| byte))
+        };
+        let select = if checkbox || radio {
+            Some(PointerSelect::Check)
+        } else if options.contains(id) {
+            if hit(&mut writer, ViewPart::Toggle0).is_some() {
+                Some(PointerSelect::Option(0))
+            } else if hit(&mut writer, ViewPart::Toggle1).is_some() {
+                Some(PointerSelect::Option(1))
+            } else {
+                None
+            }
+        } else if text {
+            if let Some(at) = hit(&mut writer, ViewPart::PreCursor) {
+                let pre = writer.text(id, ViewPart::PreCursor);
+                let count = pre.chars().count();
+                let k = at.map_or(count, |at| chars_before(&pre, at));
+                Some(PointerSelect::MoveCursor(k as isize - count as isize))
+            } else if let Some(at) = hit(&mut writer, ViewPart::Cursor) {
+                Some(PointerSelect::MoveCursor(
+                    at.is_some_and(|at| at > 0) as isize
+                ))
+            } else if let Some(at) = hit(&mut writer, ViewPart::PostCursor) {
+                let post = writer.text(id, ViewPart::PostCursor);
+                let k = at.map_or(0, |at| chars_before(&post, at));
+                Some(PointerSelect::MoveCursor(1 + k as isize))
+            } else {
+                None
+            }
+        } else {
+            None
+        };
+        if let Some(select) = select {
+            commands.trigger_targets(select, id);
+        }
+    }
+}
+
+/// Is `point` over the node?
+fn contains(point: Vec2, node: &ComputedNode, transform: &GlobalTransform) -> bool {
+    Rect::from_center_size(transform.translation().truncate(), node.size()).contains(point)
+}
+
+/// Show the hover color behind a view under the pointer.
+fn hover_view(query: HoverQuery<View>, palette: Res<Palette>, mut commands: Commands) {
+    set_hover(&query, palette.hover.into(), &mut commands);
+}
+
+/// Mark a view changed when its focus changes so it is drawn again.
+fn focus_changed<E: Event>(trigger: Trigger<E>, mut views: Query<&mut View>) {
+    if let Ok(mut view) = views.get_mut(trigger.target()) {
+        view.set_changed();
+    }
+}
+
 pub(crate) fn prompt_view(
     mut writer: ViewWriter,
     mut query: Query<(Entity, &Prompt), (With<View>, Changed<Prompt>)>,
@@ -240,17 +410,21 @@ pub(crate) fn feedback_view(
     }
 }
 
+/// Clear feedback when `T` changes unless the feedback is just as new, e.g.,
+/// from a live [Validator].
 pub(crate) fn clear_feedback<T: Component>(
     mut query: Query<&mut Feedback, (With<View>, Changed<T>)>,
 ) {
     for mut feedback in query.iter_mut() {
-        feedback.clear();
+        if !feedback.is_changed() {
+            feedback.clear();
+        }
     }
 }
 
 pub(crate) fn focus_view(
     focus: Focus,
-    mut query: Query<Entity, Or<(Changed<View>, Changed<Focusable>)>>,
+    mut query: Query<Entity, Changed<View>>,
     mut writer: ViewWriter,
 ) {
     for id in query.iter_mut() {
@@ -262,22 +436,27 @@ pub(crate) fn focus_view(
 
 /// Displays a [StringCursor] matching a query filter.
 pub fn text_view<F: bevy::ecs::query::QueryFilter>(
-    query: Query<
-        (Entity, &StringCursor, Option<&Placeholder>),
+    mut query: Query<
         (
-            With<View>,
-            F,
-            Or<(Changed<StringCursor>, Changed<Focusable>)>,
+            Entity,
+            &StringCursor,
+            Option<&Placeholder>,
+            Option<&mut SingleLine>,
         ),
+        (With<View>, F, Or<(Changed<StringCursor>, Changed<View>)>),
     >,
     palette: Res<Palette>,
     mut commands: Commands,
     mut writer: ViewWriter,
 ) {
-    for (id, text_state, placeholder) in query.iter() {
-        writer
-            .text(id, ViewPart::PreCursor)
-            .replace_range(.., &text_state.value[0..text_state.index]);
+    for (id, text_state, placeholder, single_line) in query.iter_mut() {
+        let window = scroll_window(text_state, single_line);
+        let mut pre = writer.text(id, ViewPart::PreCursor);
+        pre.clear();
+        if window.clipped_start {
+            pre.push_str(ELLIPSIS);
+        }
+        pre.push_str(&text_state.value[window.start..text_state.index]);
         writer.text(id, ViewPart::Cursor).replace_range(
             ..,
             if text_state.value.is_empty() && placeholder.is_some() {
@@ -299,9 +478,13 @@ pub fn text_view<F: bevy::ecs::query::QueryFilter>(
                 .replace_range(.., &p[ceil_char_boundary(p, 1)..]);
             writer.color(id, ViewPart::PostCursor).0 = palette.lowlight.into();
         } else {
-            writer
-                .text(id, ViewPart::PostCursor)
-                .replace_range(.., &text_state.value[text_state.next_index()..]);
+            let next = text_state.next_index();
+            let mut post = writer.text(id, ViewPart::PostCursor);
+            post.clear();
+            post.push_str(&text_state.value[next..window.end.max(next)]);
+            if window.clipped_end {
+                post.push_str(ELLIPSIS);
+            }
             writer.color(id, ViewPart::PostCursor).0 = palette.text_color.into();
         }
     }
@@ -309,23 +492,34 @@ pub fn text_view<F: bevy::ecs::query::QueryFilter>(
 
 /// Displays a [StringCursor] matching a query filter.
 pub fn opaque_view<F: bevy::ecs::query::QueryFilter>(
-    query: Query<
-        (Entity, &StringCursor, Option<&Placeholder>),
+    mut query: Query<
         (
-            With<View>,
-            F,
-            Or<(Changed<StringCursor>, Changed<Focusable>)>,
+            Entity,
+            &StringCursor,
+            Option<&Placeholder>,
+            Option<&mut SingleLine>,
         ),
+        (With<View>, F, Or<(Changed<StringCursor>, Changed<View>)>),
     >,
     palette: Res<Palette>,
     mut commands: Commands,
     mut writer: ViewWriter,
 ) {
-    for (id, text_state, placeholder) in query.iter() {
+    for (id, text_state, placeholder, single_line) in query.iter_mut() {
         let glyph = "*";
+        let window = scroll_window(text_state, single_line);
         let mut pre = writer.text(id, ViewPart::PreCursor);
         pre.clear();
-        let _ = write_rep(&mut *pre, glyph, text_state.index);
+        if window.clipped_start {
+            pre.push_str(ELLIPSIS);
+        }
+        let _ = write_rep(
+            &mut *pre,
+            glyph,
+            text_state.value[window.start..text_state.index]
+                .chars()
+                .count(),
+        );
         let mut cursor = writer.text(id, ViewPart::Cursor);
         cursor.clear();
         if text_state.value.is_empty() && placeholder.is_some() {
@@ -346,20 +540,181 @@ pub fn opaque_view<F: bevy::ecs::query::QueryFilter>(
                 .replace_range(.., &p[ceil_char_boundary(p, 1)..]);
             writer.color(id, ViewPart::PostCursor).0 = palette.lowlight.into();
         } else {
+            let next = text_state.next_index();
             let mut post = writer.text(id, ViewPart::PostCursor);
             post.clear();
             let _ = write_rep(
                 &mut *post,
                 glyph,
-                text_state.value.len().saturating_sub(text_state.index + 1),
+                text_state.value[next..window.end.max(next)].chars().count(),
             );
+            if window.clipped_end {
+                post.push_str(ELLIPSIS);
+            }
             writer.color(id, ViewPart::PostCursor).0 = palette.text_color.into();
         }
     }
 }
 
+fn scroll_window(text_state: &StringCursor, single_line: Option<Mut<SingleLine>>) -> ScrollWindow {
+    match single_line {
+        Some(mut single_line) => {
+            let width = single_line.width;
+            text_state.scroll_window(&mut single_line.scroll, width)
+        }
+        None => ScrollWindow {
+            start: 0,
+            end: text_state.value.len(),
+            clipped_start: false,
+            clipped_end: false,
+        },
+    }
+}
+
+/// Keep [SingleLine] views on one line.
+fn single_line_layout(
+    query: Query<Entity, (With<View>, Added<SingleLine>)>,
+    mut node: Query<&mut Node>,
+    mut commands: Commands,
+    mut writer: ViewWriter,
+) {
+    for id in &query {
+        if let Ok(mut node) = node.get_mut(id) {
+            node.flex_wrap = FlexWrap::NoWrap;
+            node.overflow = Overflow::clip_x();
+        }
+        for part in [ViewPart::PreCursor, ViewPart::Cursor, ViewPart::PostCursor] {
+            commands
+                .entity(writer.entity(id, part))
+                .insert(TextLayout::new_with_no_wrap());
+        }
+    }
+}
+
+/// Displays the input method's composing text at the cursor.
+pub(crate) fn preedit_view(
+    query: Query<(Entity, &Preedit), (With<View>, Changed<Preedit>)>,
+    mut writer: ViewWriter,
+) {
+    for (id, preedit) in &query {
+        writer
+            .text(id, ViewPart::Preedit)
+            .replace_range(.., &preedit.value);
+    }
+}
+
+/// Previews the normalized duration as it is typed.
+pub(crate) fn duration_view(
+    query: Query<
+        (Entity, &StringCursor),
+        (With<View>, With<DurationPrompt>, Changed<StringCursor>),
+    >,
+    mut writer: ViewWriter,
+) {
+    for (id, text_state) in &query {
+        let mut text = writer.text(id, ViewPart::Preview);
+        text.clear();
+        if let Ok(duration) = parse_duration(&text_state.value) {
+            let _ = write!(text, "= {}", format_duration(duration));
+        }
+    }
+}
+
+/// Shows the answer of an answered [Editable] prompt in place of its input
+/// while it is unfocused.
+pub(crate) fn answer_view(
+    query: Query<
+        (
+            Entity,
+            Has<Unanswered>,
+            Option<&StringCursor>,
+            Has<Password>,
+            Has<DurationPrompt>,
+            Option<&Confirm>,
+            Option<&Toggle>,
+        ),
+        (
+            With<View>,
+            With<Editable>,
+            Or<(
+                Changed<View>,
+                Changed<StringCursor>,
+                Changed<Confirm>,
+                Changed<Toggle>,
+            )>,
+        ),
+    >,
+    focus: Focus,
+    palette: Res<Palette>,
+    mut node: Query<&mut Node>,
+    mut writer: ViewWriter,
+) {
+    for (id, unanswered, text_state, password, duration, confirm, toggle) in &query {
+        let show = !unanswered && !focus.is_focused(id);
+        let mut answer = String::new();
+        if show {
+            let option: Option<&dyn OptionPrompt> = match (confirm, toggle) {
+                (Some(confirm), _) => Some(confirm),
+                (None, Some(toggle)) => Some(toggle),
+                (None, None) => None,
+            };
+            if let Some(option) = option {
+                answer.push_str(option.name(option.state()));
+            } else if let Some(text_state) = text_state {
+                if password {
+                    let _ = write_rep(&mut answer, "*", text_state.value.chars().count());
+                } else if let Some(value) = duration
+                    .then(|| parse_duration(&text_state.value).ok())
+                    .flatten()
+                {
+                    answer.push_str(&format_duration(value));
+                } else {
+                    answer.push_str(&text_state.value);
+                }
+            }
+        }
+        writer.text(id, ViewPart::Answer).replace_range(.., &answer);
+        *writer.color(id, ViewPart::Answer) = palette.answer.into();
+        for part in [
+            ViewPart::PreCursor,
+            ViewPart::Preedit,
+            ViewPart::Cursor,
+            ViewPart::PostCursor,
+            ViewPart::Preview,
+        ] {
+            if let Ok(mut node) = node.get_mut(writer.entity(id, part)) {
+                node.display = if show { Display::None } else { Display::Flex };
+            }
+        }
+    }
+}
+
+/// Place the input method's candidate box under the focused cursor.
+fn ime_position(
+    focus: Focus,
+    query: Query<Entity, (With<View>, With<Preedit>)>,
+    nodes: Query<(&ComputedNode, &GlobalTransform)>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+    mut writer: ViewWriter,
+) {
+    let Some(id) = query.iter().find(|id| focus.is_focused(*id)) else {
+        return;
+    };
+    let Ok((node, transform)) = nodes.get(writer.entity(id, ViewPart::Cursor)) else {
+        return;
+    };
+    let rect = Rect::from_center_size(transform.translation().xy(), node.size());
+    // UI nodes are laid out in physical pixels; the window wants logical ones.
+    let position = Vec2::new(rect.min.x, rect.max.y) * node.inverse_scale_factor();
+    for mut window in &mut windows {
+        if window.ime_enabled && window.ime_position != position {
+            window.ime_position = position;
+        }
+    }
+}
+
 pub(crate) fn option_view<C: Component + OptionPrompt>(
-    mut query: Query<(Entity, &C), (With<View>, Or<(Changed<Focusable>, Changed<C>)>)>,
+    mut query: Query<(Entity, &C), (With<View>, Or<(Changed<View>, Changed<C>)>)>,
     palette: Res<Palette>,
     mut commands: Commands,
     mut writer: ViewWriter,
@@ -425,10 +780,7 @@ pub(crate) fn option_view<C: Component + OptionPrompt>(
 }
 
 pub(crate) fn checkbox_view(
-    mut query: Query<
-        (Entity, &Checkbox),
-        (With<View>, Or<(Changed<Checkbox>, Changed<Focusable>)>),
-    >,
+    mut query: Query<(Entity, &Checkbox), (With<View>, Or<(Changed<Checkbox>, Changed<View>)>)>,
     palette: Res<Palette>,
     mut writer: ViewWriter,
     focus: Focus,
@@ -446,7 +798,7 @@ pub(crate) fn checkbox_view(
 }
 
 pub(crate) fn radio_view(
-    mut query: Query<(Entity, &Radio), (With<View>, Or<(Changed<Radio>, Changed<Focusable>)>)>,
+    mut query: Query<(Entity, &Radio), (With<View>, Or<(Changed<Radio>, Changed<View>)>)>,
     palette: Res<Palette>,
     mut writer: ViewWriter,
     focus: Focus,
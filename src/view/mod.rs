@@ -9,9 +9,17 @@ pub mod click;
 #[cfg(feature = "color")]
 pub mod color;
 // pub(crate) mod interaction;
+#[cfg(any(feature = "color", feature = "ascii"))]
+pub mod pointer;
 pub mod widget;
 
-pub(crate) fn plugin(_app: &mut App) {}
+#[cfg(any(feature = "color", feature = "ascii"))]
+pub use pointer::PointerSelect;
+
+pub(crate) fn plugin(_app: &mut App) {
+    #[cfg(any(feature = "color", feature = "ascii"))]
+    _app.add_plugins(pointer::plugin);
+}
 
 // /// Replace or insert a [TextSection] at a particular index.
 // pub fn replace_or_insert(text: &mut Text, index: usize, replacement: &str) {
@@ -0,0 +1,192 @@
+//! Pointer hover and click on prompts
+//!
+//! Built on [Interaction]. Pressing a [Focusable] focuses it. Views turn a
+//! press on part of a focused prompt into a [PointerSelect], which the prompt
+//! applies like the equivalent keys.
+use crate::{prelude::*, string_cursor::*};
+use bevy::{prelude::*, text::TextLayoutInfo, window::PrimaryWindow};
+
+pub(crate) fn plugin(app: &mut App) {
+    app.add_observer(add_interaction)
+        .add_observer(move_text_cursor)
+        .add_systems(Update, click_to_focus.in_set(AskySet::Controller));
+}
+
+/// Triggered on a prompt when the pointer selects part of it
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointerSelect {
+    /// Select an option by index, e.g., of a [Confirm] or [Toggle]
+    Option(usize),
+    /// Check a [Radio] or toggle a [Checkbox]
+    Check,
+    /// Move the text cursor by a number of characters
+    MoveCursor(isize),
+}
+
+/// Track the pointer over every [Focusable].
+fn add_interaction(trigger: Trigger<OnAdd, Focusable>, mut commands: Commands) {
+    commands
+        .entity(trigger.target())
+        .try_insert_if_new(Interaction::default());
+}
+
+/// Focus a [Focusable] when it is pressed.
+pub(crate) fn click_to_focus(
+    query: Query<(Entity, &Interaction), (With<Focusable>, Changed<Interaction>)>,
+    mut focus: FocusParam,
+) {
+    for (id, interaction) in &query {
+        if *interaction == Interaction::Pressed && !focus.is_focused(id) && !focus.is_blocked(id) {
+            focus.move_focus_to(id);
+        }
+    }
+}
+
+fn move_text_cursor(
+    trigger: Trigger<PointerSelect>,
+    mut query: Query<&mut StringCursor, Without<Validating>>,
+) {
+    let PointerSelect::MoveCursor(count) = *trigger.event() else {
+        return;
+    };
+    let Ok(mut text_state) = query.get_mut(trigger.target()) else {
+        return;
+    };
+    for _ in 0..count.unsigned_abs() {
+        text_state.move_cursor(if count < 0 {
+            CursorDirection::Left
+        } else {
+            CursorDirection::Right
+        });
+    }
+}
+
+/// The background a view had before the pointer hovered it
+#[derive(Component, Debug, Clone, Copy)]
+pub(crate) struct Unhovered(Option<BackgroundColor>);
+
+/// Views marked `V` whose [Interaction] changed, with their background
+pub(crate) type HoverQuery<'w, 's, V> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Interaction,
+        Option<&'static BackgroundColor>,
+        Option<&'static Unhovered>,
+    ),
+    (With<V>, Changed<Interaction>),
+>;
+
+/// Show `hover` behind views under the pointer and restore their own
+/// background once it leaves.
+pub(crate) fn set_hover<V: Component>(
+    query: &HoverQuery<V>,
+    hover: Color,
+    commands: &mut Commands,
+) {
+    for (id, interaction, background, unhovered) in query {
+        let mut entity = commands.entity(id);
+        match interaction {
+            Interaction::None => {
+                let Some(&Unhovered(background)) = unhovered else {
+                    continue;
+                };
+                entity.remove::<Unhovered>();
+                match background {
+                    Some(background) => entity.insert(background),
+                    None => entity.remove::<BackgroundColor>(),
+                };
+            }
+            Interaction::Hovered | Interaction::Pressed => {
+                if unhovered.is_none() {
+                    entity.insert(Unhovered(background.copied()));
+                }
+                entity.insert(BackgroundColor(hover));
+            }
+        }
+    }
+}
+
+/// Return the pointer position on the primary window in physical pixels.
+pub(crate) fn pointer_position(windows: &Query<&Window, With<PrimaryWindow>>) -> Option<Vec2> {
+    windows.single().ok()?.physical_cursor_position()
+}
+
+/// Return the span, the text entity itself being 0, and the byte index in
+/// the line of the text position nearest `point`, if the node has any glyphs.
+/// The byte is after the glyph if `point` is past its middle.
+pub(crate) fn text_hit(
+    point: Vec2,
+    node: &ComputedNode,
+    transform: &GlobalTransform,
+    layout: &TextLayoutInfo,
+) -> Option<(usize, usize)> {
+    let local = point - (transform.translation().truncate() - 0.5 * node.size());
+    let line = layout
+        .glyphs
+        .iter()
+        .min_by(|a, b| {
+            (a.position.y - local.y)
+                .abs()
+                .total_cmp(&(b.position.y - local.y).abs())
+        })?
+        .line_index;
+    let glyph = layout
+        .glyphs
+        .iter()
+        .filter(|glyph| glyph.line_index == line)
+        .min_by(|a, b| {
+            (a.position.x - local.x)
+                .abs()
+                .total_cmp(&(b.position.x - local.x).abs())
+        })?;
+    let after = local.x > glyph.position.x;
+    Some((
+        glyph.span_index,
+        glyph.byte_index + if after { glyph.byte_length } else { 0 },
+    ))
+}
+
+/// Return how many characters of `text` come before byte `byte`.
+pub(crate) fn chars_before(text: &str, byte: usize) -> usize {
+    text[..floor_char_boundary(text, byte)].chars().count()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+
+    #[derive(Component)]
+    struct Marker;
+
+    fn hover(world: &mut World) {
+        world
+            .run_system_once(|query: HoverQuery<Marker>, mut commands: Commands| {
+                set_hover(&query, Color::WHITE, &mut commands)
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn hover_restores_background() {
+        let mut world = World::new();
+        let red = BackgroundColor(Color::srgb(1.0, 0.0, 0.0));
+        let id = world.spawn((Marker, Interaction::Hovered, red)).id();
+        hover(&mut world);
+        assert_eq!(world.get(id), Some(&BackgroundColor(Color::WHITE)));
+
+        *world.get_mut::<Interaction>(id).unwrap() = Interaction::None;
+        hover(&mut world);
+        assert_eq!(world.get(id), Some(&red));
+
+        // Without a background before, none is left after.
+        world.entity_mut(id).remove::<BackgroundColor>();
+        *world.get_mut::<Interaction>(id).unwrap() = Interaction::Pressed;
+        hover(&mut world);
+        *world.get_mut::<Interaction>(id).unwrap() = Interaction::None;
+        hover(&mut world);
+        assert!(world.get::<BackgroundColor>(id).is_none());
+    }
+}